@@ -0,0 +1,73 @@
+//! Configuration.
+
+use mdx_highlighter::config::HighlighterConfig;
+
+/// Configuration (optional).
+///
+/// Wraps the options of `mdxjs` and adds the configuration of the
+/// highlighter next to them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase", default))]
+pub struct Options {
+    /// Configuration that describes how to compile MDX.
+    #[cfg_attr(feature = "serializable", serde(flatten))]
+    pub mdx: mdxjs::Options,
+
+    /// Configuration of the code highlighter (default:
+    /// `Some(HighlighterConfig::default())`).
+    ///
+    /// When `None`, code blocks are left untouched.
+    pub highlighter: Option<HighlighterConfig>,
+}
+
+impl Default for Options {
+    /// Default options of `mdxjs` with highlighting turned on.
+    fn default() -> Self {
+        Self {
+            mdx: mdxjs::Options::default(),
+            highlighter: Some(HighlighterConfig::default()),
+        }
+    }
+}
+
+impl Options {
+    /// MDX with GFM.
+    ///
+    /// See [`mdxjs::Options::gfm`][] for more information.
+    pub fn gfm() -> Self {
+        Self {
+            mdx: mdxjs::Options::gfm(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let options = Options::default();
+        assert_eq!(
+            options.highlighter,
+            Some(HighlighterConfig::default()),
+            "should highlight by default"
+        );
+        assert!(
+            !options.mdx.parse.constructs.gfm_autolink_literal,
+            "should default to `CommonMark`"
+        );
+
+        let options = Options::gfm();
+        assert!(
+            options.highlighter.is_some(),
+            "should support `gfm` shortcut (1)"
+        );
+        assert!(
+            options.mdx.parse.constructs.gfm_autolink_literal,
+            "should support `gfm` shortcut (2)"
+        );
+    }
+}
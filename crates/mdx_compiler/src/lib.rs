@@ -4,6 +4,8 @@
 //!
 //! *   [`compile()`][]
 //!     — turn MDX into JavaScript
+//! *   [`compile_with_warnings()`][]
+//!     — turn MDX into JavaScript, with warnings about its code blocks
 #![deny(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::too_many_lines)]
@@ -12,13 +14,14 @@
 #![allow(clippy::cast_precision_loss)]
 
 extern crate markdown;
-use mdxjs::{ hast_util_to_swc, mdast_util_to_hast, mdx_plugin_recma_document, mdx_plugin_recma_jsx_rewrite, swc, swc_util_build_jsx, configuration };
+mod configuration;
+
+use mdxjs::{ hast_util_to_swc, mdast_util_to_hast, mdx_plugin_recma_document, mdx_plugin_recma_jsx_rewrite, swc, swc_util_build_jsx };
 
 use mdx_highlighter;
-use crate::mdx_highlighter::{ mdx_plugin_highlighter, config::HighlighterConfig };
+use crate::mdx_highlighter::mdx_plugin_highlighter;
 
 use crate::{
-    configuration::{Options},
     hast_util_to_swc::hast_util_to_swc,
     mdast_util_to_hast::mdast_util_to_hast,
     mdx_plugin_recma_document::{mdx_plugin_recma_document, Options as DocumentOptions},
@@ -29,9 +32,15 @@ use crate::{
 
 use markdown::{to_mdast, Constructs, Location, ParseOptions};
 
+pub use crate::configuration::Options;
+pub use crate::mdx_highlighter::config::HighlighterConfig;
+pub use crate::mdx_highlighter::warning::Warning;
 
 /// Turn MDX into JavaScript.
 ///
+/// Warnings about code blocks are left out; use
+/// [`compile_with_warnings()`][] to get them.
+///
 /// ## Examples
 ///
 /// ```
@@ -48,74 +57,95 @@ use markdown::{to_mdast, Constructs, Location, ParseOptions};
 /// This project errors for many different reasons, such as syntax errors in
 /// the MDX format or misconfiguration.
 pub fn compile(value: &str, options: &Options) -> Result<String, String> {
+    compile_with_warnings(value, options).map(|(code, _)| code)
+}
+
+/// Turn MDX into JavaScript, and report problems in its code blocks that did
+/// not stop highlighting, such as line ranges out of their code.
+///
+/// Use this instead of [`compile()`][] to show warnings to authors.
+///
+/// ## Errors
+///
+/// This errors for the same reasons as [`compile()`][].
+pub fn compile_with_warnings(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<Warning>), String> {
     let parse_options = ParseOptions {
         constructs: Constructs {
-            attention: options.parse.constructs.attention,
+            attention: options.mdx.parse.constructs.attention,
             autolink: false,
-            block_quote: options.parse.constructs.block_quote,
-            character_escape: options.parse.constructs.character_escape,
-            character_reference: options.parse.constructs.character_reference,
-            code_fenced: options.parse.constructs.code_fenced,
+            block_quote: options.mdx.parse.constructs.block_quote,
+            character_escape: options.mdx.parse.constructs.character_escape,
+            character_reference: options.mdx.parse.constructs.character_reference,
+            code_fenced: options.mdx.parse.constructs.code_fenced,
             code_indented: false,
-            code_text: options.parse.constructs.code_text,
-            definition: options.parse.constructs.definition,
-            frontmatter: options.parse.constructs.frontmatter,
-            gfm_autolink_literal: options.parse.constructs.gfm_autolink_literal,
-            gfm_footnote_definition: options.parse.constructs.gfm_footnote_definition,
-            gfm_label_start_footnote: options.parse.constructs.gfm_label_start_footnote,
-            gfm_strikethrough: options.parse.constructs.gfm_strikethrough,
-            gfm_table: options.parse.constructs.gfm_table,
-            gfm_task_list_item: options.parse.constructs.gfm_task_list_item,
-            hard_break_escape: options.parse.constructs.hard_break_escape,
-            hard_break_trailing: options.parse.constructs.hard_break_trailing,
+            code_text: options.mdx.parse.constructs.code_text,
+            definition: options.mdx.parse.constructs.definition,
+            frontmatter: options.mdx.parse.constructs.frontmatter,
+            gfm_autolink_literal: options.mdx.parse.constructs.gfm_autolink_literal,
+            gfm_footnote_definition: options.mdx.parse.constructs.gfm_footnote_definition,
+            gfm_label_start_footnote: options.mdx.parse.constructs.gfm_label_start_footnote,
+            gfm_strikethrough: options.mdx.parse.constructs.gfm_strikethrough,
+            gfm_table: options.mdx.parse.constructs.gfm_table,
+            gfm_task_list_item: options.mdx.parse.constructs.gfm_task_list_item,
+            hard_break_escape: options.mdx.parse.constructs.hard_break_escape,
+            hard_break_trailing: options.mdx.parse.constructs.hard_break_trailing,
             html_flow: false,
             html_text: false,
-            heading_atx: options.parse.constructs.heading_atx,
-            heading_setext: options.parse.constructs.heading_setext,
-            label_start_image: options.parse.constructs.label_start_image,
-            label_start_link: options.parse.constructs.label_start_link,
-            label_end: options.parse.constructs.label_end,
-            list_item: options.parse.constructs.list_item,
-            math_flow: options.parse.constructs.math_flow,
-            math_text: options.parse.constructs.math_text,
+            heading_atx: options.mdx.parse.constructs.heading_atx,
+            heading_setext: options.mdx.parse.constructs.heading_setext,
+            label_start_image: options.mdx.parse.constructs.label_start_image,
+            label_start_link: options.mdx.parse.constructs.label_start_link,
+            label_end: options.mdx.parse.constructs.label_end,
+            list_item: options.mdx.parse.constructs.list_item,
+            math_flow: options.mdx.parse.constructs.math_flow,
+            math_text: options.mdx.parse.constructs.math_text,
             mdx_esm: true,
             mdx_expression_flow: true,
             mdx_expression_text: true,
             mdx_jsx_flow: true,
             mdx_jsx_text: true,
-            thematic_break: options.parse.constructs.thematic_break,
+            thematic_break: options.mdx.parse.constructs.thematic_break,
         },
-        gfm_strikethrough_single_tilde: options.parse.gfm_strikethrough_single_tilde,
-        math_text_single_dollar: options.parse.math_text_single_dollar,
+        gfm_strikethrough_single_tilde: options.mdx.parse.gfm_strikethrough_single_tilde,
+        math_text_single_dollar: options.mdx.parse.math_text_single_dollar,
         mdx_esm_parse: Some(Box::new(parse_esm)),
         mdx_expression_parse: Some(Box::new(parse_expression)),
     };
     let document_options = DocumentOptions {
-        pragma: options.pragma.clone(),
-        pragma_frag: options.pragma_frag.clone(),
-        pragma_import_source: options.pragma_import_source.clone(),
-        jsx_import_source: options.jsx_import_source.clone(),
-        jsx_runtime: options.jsx_runtime,
+        pragma: options.mdx.pragma.clone(),
+        pragma_frag: options.mdx.pragma_frag.clone(),
+        pragma_import_source: options.mdx.pragma_import_source.clone(),
+        jsx_import_source: options.mdx.jsx_import_source.clone(),
+        jsx_runtime: options.mdx.jsx_runtime,
     };
     let rewrite_options = RewriteOptions {
-        development: options.development,
-        provider_import_source: options.provider_import_source.clone(),
+        development: options.mdx.development,
+        provider_import_source: options.mdx.provider_import_source.clone(),
     };
     let build_options = BuildOptions {
-        development: options.development,
+        development: options.mdx.development,
     };
 
     let location = Location::new(value.as_bytes());
     let mdast = to_mdast(value, &parse_options)?;
     let mut hast = mdast_util_to_hast(&mdast);
-    mdx_plugin_highlighter(&mut hast, &HighlighterConfig::default()); //TODO: Allow external configuration
-    let mut program = hast_util_to_swc(&hast, options.filepath.clone(), Some(&location))?;
+    let warnings = match options.highlighter.as_ref() {
+        Some(highlighter_options) => mdx_plugin_highlighter(&mut hast, highlighter_options),
+        None => vec![],
+    };
+    let mut program = hast_util_to_swc(&hast, options.mdx.filepath.clone(), Some(&location))?;
     mdx_plugin_recma_document(&mut program, &document_options, Some(&location))?;
     mdx_plugin_recma_jsx_rewrite(&mut program, &rewrite_options, Some(&location));
 
-    if !options.jsx {
+    if !options.mdx.jsx {
         swc_util_build_jsx(&mut program, &build_options, Some(&location))?;
     }
 
-    Ok(serialize(&mut program.module, Some(&program.comments)))
+    Ok((
+        serialize(&mut program.module, Some(&program.comments)),
+        warnings,
+    ))
 }
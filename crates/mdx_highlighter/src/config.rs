@@ -1,11 +1,50 @@
+//! Configuration.
+
+use mdx_lexers::{ClassNames, Theme};
+
+/// Configuration for the highlighter (optional).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase", default))]
 pub struct HighlighterConfig {
-    pub line_number: bool
+    /// Whether to show line numbers (default: `true`).
+    ///
+    /// Exposed to stylesheets through the `data-line-numbers` attribute on
+    /// the code wrapper.
+    ///
+    /// Code fences with the `showLineNumbers` flag in their meta always show
+    /// them.
+    pub line_number: bool,
+
+    /// Class names of code blocks, lines and tokens (default:
+    /// `ClassNames::default()`, prefixed with `hl`).
+    ///
+    /// Lines selected in the meta of a code fence, such as `{1,3-5}` in
+    /// ```` ```js {1,3-5} ````, get the class name of highlighted lines.
+    pub class_names: ClassNames,
+
+    /// How tokens are styled (default: `HighlighterStyle::ClassNames`).
+    pub style: HighlighterStyle,
+}
+
+/// How highlighted code is styled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase"))]
+pub enum HighlighterStyle {
+    /// Add class names to code, lines and tokens, styled by a stylesheet.
+    ClassNames,
+    /// Add `style` properties with the colours of a theme, so that code can
+    /// be rendered without a stylesheet (for example in emails or feeds).
+    Inline(Theme),
 }
 
 impl Default for HighlighterConfig {
     fn default() -> Self {
         Self {
-            line_number: true
+            line_number: true,
+            class_names: ClassNames::default(),
+            style: HighlighterStyle::ClassNames,
         }
     }
 }
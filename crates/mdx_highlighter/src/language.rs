@@ -1,26 +1,92 @@
+use mdx_lexers::SqlDialect;
 use mdxjs::hast;
 
-pub fn get_language(properties: Vec<(String, hast::PropertyValue)>) -> String {
+use crate::meta::CodeMeta;
 
+/// Language of a code element, from its `language-*` class name (`ts` for
+/// `language-ts`, `diff-ts` for `language-diff-ts`).
+pub fn get_language(properties: Vec<(String, hast::PropertyValue)>) -> String {
     let property = properties.iter().find(|property| property.0 == "className");
 
     if let Some(class_name) = property {
         if let hast::PropertyValue::String(value) = &class_name.1 {
-            if value.starts_with("language-") {
-                let language = value.split("-").nth(1).unwrap_or("");
+            if let Some(language) = value.strip_prefix("language-") {
                 return language.to_string();
             }
-        }
-        else if let hast::PropertyValue::SpaceSeparated(values) = &class_name.1 {
-            let language_class_name = values.iter().find(|value| value.starts_with("language-"));
-            if let Some(language) = language_class_name {
-                if language.starts_with("language-") {
-                    let language = language.split("-").nth(1).unwrap_or("");
-                    return language.to_string();
-                }
+        } else if let hast::PropertyValue::SpaceSeparated(values) = &class_name.1 {
+            let language = values.iter().find_map(|value| value.strip_prefix("language-"));
+            if let Some(language) = language {
+                return language.to_string();
             }
         }
     }
 
     String::from("")
 }
+
+/// Language with the dialect in the meta of its fence: `sql dialect=mysql`
+/// and `sql mysql` are highlighted as `mysql`.
+pub fn with_dialect(language: String, meta: &CodeMeta) -> String {
+    if !language.eq_ignore_ascii_case("sql") {
+        return language;
+    }
+
+    meta.get("dialect")
+        .into_iter()
+        .chain(meta.flags.iter().map(String::as_str))
+        .find(|name| SqlDialect::from_name(name).is_some())
+        .map(String::from)
+        .unwrap_or(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn class_name(value: hast::PropertyValue) -> Vec<(String, hast::PropertyValue)> {
+        vec![("className".into(), value)]
+    }
+
+    #[test]
+    fn language() {
+        assert_eq!(
+            get_language(class_name(hast::PropertyValue::String("language-js".into()))),
+            "js",
+            "should get the language of a class name"
+        );
+        assert_eq!(
+            get_language(class_name(hast::PropertyValue::SpaceSeparated(vec![
+                "a".into(),
+                "language-diff-ts".into()
+            ]))),
+            "diff-ts",
+            "should keep dashes in the language"
+        );
+        assert_eq!(get_language(vec![]), "", "should default to no language");
+    }
+
+    #[test]
+    fn dialect() {
+        assert_eq!(
+            with_dialect("sql".into(), &CodeMeta::parse("title=\"a.sql\" dialect=mysql")),
+            "mysql",
+            "should use the dialect attribute"
+        );
+        assert_eq!(
+            with_dialect("SQL".into(), &CodeMeta::parse("{1} postgres")),
+            "postgres",
+            "should use dialect flags"
+        );
+        assert_eq!(
+            with_dialect("sql".into(), &CodeMeta::parse("showLineNumbers")),
+            "sql",
+            "should ignore other flags"
+        );
+        assert_eq!(
+            with_dialect("js".into(), &CodeMeta::parse("mysql")),
+            "js",
+            "should only change SQL"
+        );
+    }
+}
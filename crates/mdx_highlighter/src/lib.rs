@@ -1,10 +1,37 @@
 pub mod config;
+pub mod meta;
+pub mod stylesheet;
+pub mod warning;
 mod visitor;
 mod language;
 
 use mdxjs::hast;
 use visitor::visit_code_mut;
+use warning::Warning;
 
-pub fn mdx_plugin_highlighter(tree: &mut hast::Node, options: &config::HighlighterConfig) {
-    visit_code_mut(tree, options);
+pub use mdx_lexers::{ClassNames, LanguageRegistry, Lexer, LineKind, Theme, TokenKind, TokenStyle};
+
+/// Highlight the code blocks of `tree` with the built-in lexers.
+///
+/// Returns warnings about the code blocks, such as line ranges out of their
+/// code, for the caller to report.
+#[must_use]
+pub fn mdx_plugin_highlighter(
+    tree: &mut hast::Node,
+    options: &config::HighlighterConfig,
+) -> Vec<Warning> {
+    mdx_plugin_highlighter_with_registry(tree, options, &LanguageRegistry::default())
+}
+
+/// Highlight the code blocks of `tree`, resolving their languages in
+/// `registry`.
+#[must_use]
+pub fn mdx_plugin_highlighter_with_registry(
+    tree: &mut hast::Node,
+    options: &config::HighlighterConfig,
+    registry: &LanguageRegistry,
+) -> Vec<Warning> {
+    let mut warnings = vec![];
+    visit_code_mut(tree, options, registry, &mut warnings);
+    warnings
 }
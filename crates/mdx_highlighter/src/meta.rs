@@ -0,0 +1,155 @@
+//! Code fence meta.
+//!
+//! The text after the language of a code fence, such as
+//! `title="api.js" {2,4-6} showLineNumbers`, is kept on the `code` element
+//! by `mdast_util_to_hast` and parsed here.
+
+use mdxjs::hast;
+
+/// Property of the `code` element with the meta of its fence.
+pub const DATA_META_PROP: &str = "dataMeta";
+
+/// Parsed meta of a code fence.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CodeMeta {
+    /// Key/value pairs (`title="api.js"`, `title=api.js`).
+    pub attributes: Vec<(String, String)>,
+    /// Words without a value (`showLineNumbers`).
+    pub flags: Vec<String>,
+    /// Inclusive, 1-indexed line ranges between braces (`{2,4-6}`).
+    pub line_ranges: Vec<(usize, usize)>,
+}
+
+impl CodeMeta {
+    /// Parse the meta of a code fence.
+    ///
+    /// Malformed ranges are dropped, everything else is kept.
+    pub fn parse(meta: &str) -> Self {
+        let chars: Vec<char> = meta.chars().collect();
+        let mut result = Self::default();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let ch = chars[index];
+            if ch.is_whitespace() {
+                index += 1;
+            } else if ch == '{' {
+                let start = index + 1;
+                while index < chars.len() && chars[index] != '}' {
+                    index += 1;
+                }
+                let ranges: String = chars[start..index].iter().collect();
+                result.line_ranges.extend(ranges.split(',').filter_map(parse_range));
+                index += 1;
+            } else {
+                let start = index;
+                while index < chars.len() && !chars[index].is_whitespace() && chars[index] != '=' {
+                    index += 1;
+                }
+                let key: String = chars[start..index].iter().collect();
+
+                if index < chars.len() && chars[index] == '=' {
+                    index += 1;
+                    let value = if index < chars.len() && (chars[index] == '"' || chars[index] == '\'') {
+                        let quote = chars[index];
+                        index += 1;
+                        let start = index;
+                        while index < chars.len() && chars[index] != quote {
+                            index += 1;
+                        }
+                        let value = chars[start..index].iter().collect();
+                        index += 1;
+                        value
+                    } else {
+                        let start = index;
+                        while index < chars.len() && !chars[index].is_whitespace() {
+                            index += 1;
+                        }
+                        chars[start..index].iter().collect()
+                    };
+                    result.attributes.push((key, value));
+                } else {
+                    result.flags.push(key);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Parse the meta kept on a `code` element, if any.
+    pub fn from_properties(properties: &[(String, hast::PropertyValue)]) -> Self {
+        let property = properties.iter().find(|property| property.0 == DATA_META_PROP);
+
+        if let Some((_, hast::PropertyValue::String(value))) = property {
+            Self::parse(value)
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Value of the attribute `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the flag `flag` is set.
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|name| name == flag)
+    }
+}
+
+/// Parse `4` or `4-6` into an inclusive range.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let range = range.trim();
+    if let Some((start, end)) = range.split_once('-') {
+        let start = start.trim().parse().ok()?;
+        let end = end.trim().parse().ok()?;
+        Some((start, end))
+    } else {
+        let line = range.parse().ok()?;
+        Some((line, line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        assert_eq!(CodeMeta::parse(""), CodeMeta::default(), "should support empty meta");
+
+        assert_eq!(
+            CodeMeta::parse("title=\"api docs.js\" {2,4-6} showLineNumbers lang=js"),
+            CodeMeta {
+                attributes: vec![
+                    ("title".into(), "api docs.js".into()),
+                    ("lang".into(), "js".into())
+                ],
+                flags: vec!["showLineNumbers".into()],
+                line_ranges: vec![(2, 2), (4, 6)],
+            },
+            "should support attributes, flags and line ranges"
+        );
+
+        assert_eq!(
+            CodeMeta::parse("{ 1, a, 3 - 4 } title='a'").line_ranges,
+            vec![(1, 1), (3, 4)],
+            "should drop malformed ranges"
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        let meta = CodeMeta::parse("title=a.js showLineNumbers");
+        assert_eq!(meta.get("title"), Some("a.js"), "should get attributes");
+        assert_eq!(meta.get("b"), None, "should not get missing attributes");
+        assert!(meta.has_flag("showLineNumbers"), "should find flags");
+        assert!(!meta.has_flag("title"), "should not find attributes as flags");
+    }
+}
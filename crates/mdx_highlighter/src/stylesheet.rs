@@ -0,0 +1,179 @@
+//! Stylesheet for class name output.
+
+use mdx_lexers::{
+    constants::{DATA_LINE_NUMBERS_ATTR, DATA_LINE_NUMBER_ATTR},
+    LineKind, Theme, TokenKind,
+};
+
+use crate::config::HighlighterConfig;
+
+/// Generate the CSS that styles code highlighted with `options` in the
+/// colours of `theme`, using the class names of `options`.
+///
+/// When `dark_theme` is given, its colours are used inside a
+/// `@media (prefers-color-scheme: dark)` block.
+pub fn stylesheet(theme: &Theme, dark_theme: Option<&Theme>, options: &HighlighterConfig) -> String {
+    let mut css = String::new();
+    let class_names = &options.class_names;
+    let code = format!(".{}", class_names.code());
+    let line = format!("{} .{}", code, class_names.line());
+
+    css.push_str(&format!("{} {{\n  overflow-x: auto;\n}}\n", code));
+    css.push_str(&format!("{} {{\n  min-height: 1em;\n  padding: 0 1em;\n}}\n", line));
+    css.push_str(&format!(
+        "{}[{}=\"true\"] .{}::before {{\n  content: attr({});\n  display: inline-block;\n  width: 2em;\n  margin-right: 1em;\n  text-align: right;\n  user-select: none;\n}}\n",
+        code,
+        DATA_LINE_NUMBERS_ATTR,
+        class_names.line(),
+        DATA_LINE_NUMBER_ATTR
+    ));
+    css.push_str(&format!(
+        "{} .{} {{\n  user-select: none;\n}}\n",
+        code,
+        class_names.line_marker()
+    ));
+    for kind in TokenKind::ALL {
+        if !kind.is_selectable() {
+            css.push_str(&format!(
+                "{} .{} {{\n  user-select: none;\n}}\n",
+                code,
+                class_names.token(kind)
+            ));
+        }
+    }
+    css.push_str(&theme_rules(theme, options));
+
+    if let Some(dark_theme) = dark_theme {
+        css.push_str("@media (prefers-color-scheme: dark) {\n");
+        for rule in theme_rules(dark_theme, options).lines() {
+            css.push_str(&format!("  {}\n", rule));
+        }
+        css.push_str("}\n");
+    }
+
+    css
+}
+
+/// Rules with the colours of `theme`.
+fn theme_rules(theme: &Theme, options: &HighlighterConfig) -> String {
+    let mut css = String::new();
+    let class_names = &options.class_names;
+    let code = format!(".{}", class_names.code());
+
+    css.push_str(&format!(
+        "{} {{\n  {};\n}}\n",
+        code,
+        theme.to_css().replace("; ", ";\n  ")
+    ));
+    css.push_str(&format!(
+        "{} .{}::before,\n{} .{} {{\n  color: {};\n}}\n",
+        code,
+        class_names.line(),
+        code,
+        class_names.line_marker(),
+        theme.line_number
+    ));
+    // Before highlighted lines, which win when a line is both
+    for kind in LineKind::ALL {
+        css.push_str(&format!(
+            "{} .{}.{} {{\n  background-color: {};\n}}\n",
+            code,
+            class_names.line(),
+            class_names.line_kind(kind),
+            theme.line_background(kind)
+        ));
+    }
+    css.push_str(&format!(
+        "{} .{}.{} {{\n  background-color: {};\n}}\n",
+        code,
+        class_names.line(),
+        class_names.highlighted_line(),
+        theme.highlighted_line
+    ));
+
+    for kind in TokenKind::ALL {
+        if let Some(style) = theme.token_style(kind) {
+            css.push_str(&format!(
+                "{} .{} {{\n  {};\n}}\n",
+                code,
+                class_names.token(kind),
+                style.to_css().replace("; ", ";\n  ")
+            ));
+        }
+    }
+
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mdx_lexers::ClassNames;
+
+    #[test]
+    fn light() {
+        let css = stylesheet(&Theme::light(), None, &HighlighterConfig::default());
+        assert!(
+            css.contains(".hl {\n  background-color: #ffffff;\n  color: #24292f;\n}\n"),
+            "should style code blocks"
+        );
+        assert!(
+            css.contains(".hl[data-line-numbers=\"true\"] .hl-l::before {\n  content: attr(data-line-number);"),
+            "should add line numbers from attributes"
+        );
+        assert!(
+            css.contains(".hl .hl-l.hl-l-hl {\n  background-color: #fff8c5;\n}\n"),
+            "should style highlighted lines"
+        );
+        assert!(
+            css.contains(".hl .hl-l.hl-l-add {\n  background-color: #e6ffec;\n}\n"),
+            "should style added lines"
+        );
+        assert!(
+            css.contains(".hl .hl-mk {\n  user-select: none;\n}\n"),
+            "should not let line markers be selected"
+        );
+        assert!(
+            css.contains(".hl .hl-cmt {\n  color: #6e7781;\n  font-style: italic;\n}\n"),
+            "should style tokens"
+        );
+        assert!(
+            css.contains(".hl .hl-pr {\n  user-select: none;\n}\n"),
+            "should not let prompts be selected"
+        );
+        assert!(!css.contains("@media"), "should not add a dark variant by default");
+    }
+
+    #[test]
+    fn class_names() {
+        let options = HighlighterConfig {
+            class_names: ClassNames {
+                prefix: "code".into(),
+                tokens: vec![(TokenKind::Keyword, "kw".into())],
+                highlighted_line: None,
+            },
+            ..HighlighterConfig::default()
+        };
+        let css = stylesheet(&Theme::light(), None, &options);
+        assert!(css.contains(".code .code-l.code-l-hl {"), "should use the prefix (1)");
+        assert!(css.contains(".code .code-s {"), "should use the prefix (2)");
+        assert!(css.contains(".code .kw {"), "should use overridden class names");
+        assert!(!css.contains(".hl"), "should not use the default prefix");
+    }
+
+    #[test]
+    fn dark_variant() {
+        let css = stylesheet(
+            &Theme::light(),
+            Some(&Theme::dark()),
+            &HighlighterConfig::default(),
+        );
+        let dark = &css[css.find("@media (prefers-color-scheme: dark) {\n").unwrap()..];
+        assert!(
+            dark.contains("  .hl {\n    background-color: #0d1117;\n    color: #c9d1d9;\n  }\n"),
+            "should add the dark theme in a media query"
+        );
+        assert!(dark.ends_with("}\n"), "should close the media query");
+    }
+}
@@ -1,43 +1,81 @@
-use mdx_lexers::constants::{DATA_LINE_NUMBERS_ATTR, CODE_CLASS_NAME};
+use mdx_lexers::constants::DATA_LINE_NUMBERS_ATTR;
 use mdxjs::hast;
-use mdx_lexers::highlight;
-
-use crate::{config, language};
-
-pub fn visit_code_mut(tree: &mut hast::Node, options: &config::HighlighterConfig) {
-    for child in tree.children_mut().unwrap_or(&mut vec![]) {
-        match child {
-            hast::Node::Element(element) => match element.tag_name.as_str() {
-                "code" => {
-                    highlight_code(element, options);
-                },
-                "pre" => {
-                    highlight_pre(element, options);
-                },
-                _ => {}
-            },
-            //Plugin just check for pre or code tag at root level
-            element => visit_code_mut(element, options)
+use mdx_lexers::{HighlighterTokenizer, LanguageRegistry};
+
+use crate::{
+    config::{self, HighlighterStyle},
+    language,
+    meta::CodeMeta,
+    warning::Warning,
+};
+
+pub fn visit_code_mut(
+    tree: &mut hast::Node,
+    options: &config::HighlighterConfig,
+    registry: &LanguageRegistry,
+    warnings: &mut Vec<Warning>,
+) {
+    if let hast::Node::Element(element) = tree {
+        if element.tag_name == "pre" {
+            highlight_pre(element, options, registry, warnings);
+            return;
+        }
+    }
+
+    //Roots, elements and MDX JSX elements are walked, other nodes can't contain code
+    if let Some(children) = tree.children_mut() {
+        for child in children {
+            visit_code_mut(child, options, registry, warnings);
         }
     }
 }
 
-fn highlight_code(element: &mut hast::Element , options: &config::HighlighterConfig) {
+fn highlight_code(
+    element: &mut hast::Element,
+    options: &config::HighlighterConfig,
+    registry: &LanguageRegistry,
+    warnings: &mut Vec<Warning>,
+) {
     if let Some(node) = element.children.first() {
         if let hast::Node::Text(text) = node {
-            let language = language::get_language(element.properties.clone());
+            let meta = CodeMeta::from_properties(&element.properties);
+            let language =
+                language::with_dialect(language::get_language(element.properties.clone()), &meta);
             let input: Vec<char> = text.value.chars().collect();
+            let line_number = options.line_number || meta.has_flag("showLineNumbers");
 
-            let code_rows = highlight(input, &language);
+            let mut tokens = HighlighterTokenizer::new();
+            tokens.class_names(options.class_names.clone());
+            tokens.highlight_lines(
+                highlighted_lines(
+                    &meta,
+                    &text.value,
+                    element
+                        .position
+                        .as_ref()
+                        .map(|position| (position.start.line, position.start.column)),
+                    warnings,
+                ),
+            );
+
+            let style_property = match &options.style {
+                HighlighterStyle::ClassNames => (
+                    "className".into(),
+                    hast::PropertyValue::String(options.class_names.code()),
+                ),
+                HighlighterStyle::Inline(theme) => {
+                    tokens.inline_styles(theme.clone());
+                    ("style".into(), hast::PropertyValue::String(theme.to_css()))
+                }
+            };
+
+            let code_rows = registry.highlight_with(input, &language, tokens);
 
             let hast = hast::Node::Element(hast::Element {
                 tag_name: "div".into(),
-                properties: vec![(
-                    "className".into(),
-                    hast::PropertyValue::String(CODE_CLASS_NAME.into()),
-                ), (
+                properties: vec![style_property, (
                     DATA_LINE_NUMBERS_ATTR.into(),
-                    hast::PropertyValue::String(options.line_number.to_string()),
+                    hast::PropertyValue::String(line_number.to_string()),
                 )],
                 children: code_rows,
                 position: None,
@@ -48,12 +86,161 @@ fn highlight_code(element: &mut hast::Element , options: &config::HighlighterCon
     }
 }
 
-fn highlight_pre(element: &mut hast::Element, options: &config::HighlighterConfig) {
+/// Line ranges of `meta` that fit in `code`.
+///
+/// Ranges out of the code are reported in `warnings` and dropped, ranges
+/// that go past its end are cut.
+fn highlighted_lines(
+    meta: &CodeMeta,
+    code: &str,
+    place: Option<(usize, usize)>,
+    warnings: &mut Vec<Warning>,
+) -> Vec<(usize, usize)> {
+    let line_count = code.lines().count();
+    let mut lines = vec![];
+
+    for &(start, end) in &meta.line_ranges {
+        if start == 0 || start > end || start > line_count {
+            warnings.push(line_range_warning(start, end, line_count, place));
+        } else {
+            if end > line_count {
+                warnings.push(line_range_warning(start, end, line_count, place));
+            }
+            lines.push((start, end.min(line_count)));
+        }
+    }
+
+    lines
+}
+
+fn line_range_warning(
+    start: usize,
+    end: usize,
+    line_count: usize,
+    place: Option<(usize, usize)>,
+) -> Warning {
+    Warning {
+        reason: format!(
+            "line range `{}-{}` is out of a code block of {} lines",
+            start, end, line_count
+        ),
+        place,
+    }
+}
+
+fn highlight_pre(
+    element: &mut hast::Element,
+    options: &config::HighlighterConfig,
+    registry: &LanguageRegistry,
+    warnings: &mut Vec<Warning>,
+) {
     if let Some(node) = element.children.first_mut() {
         if let hast::Node::Element(child) = node {
             if child.tag_name == "code" {
-                highlight_code(child, options);
+                highlight_code(child, options, registry, warnings);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_block(value: &str) -> hast::Node {
+        hast::Node::Element(hast::Element {
+            tag_name: "pre".into(),
+            properties: vec![],
+            children: vec![hast::Node::Element(hast::Element {
+                tag_name: "code".into(),
+                properties: vec![(
+                    "className".into(),
+                    hast::PropertyValue::SpaceSeparated(vec!["language-js".into()]),
+                )],
+                children: vec![hast::Node::Text(hast::Text {
+                    value: value.into(),
+                    position: None,
+                })],
+                position: None,
+            })],
+            position: None,
+        })
+    }
+
+    fn is_highlighted(node: &hast::Node) -> bool {
+        if let hast::Node::Element(pre) = node {
+            if let Some(hast::Node::Element(code)) = pre.children.first() {
+                if let Some(hast::Node::Element(wrapper)) = code.children.first() {
+                    return wrapper.tag_name == "div";
+                }
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn validate_line_ranges() {
+        let meta = CodeMeta::parse("{0,2,3-9,5-6,4-3}");
+        let mut warnings = vec![];
+        assert_eq!(
+            highlighted_lines(&meta, "a\nb\nc\n", Some((1, 1)), &mut warnings),
+            vec![(2, 2), (3, 3)],
+            "should drop and cut ranges out of the code"
+        );
+        assert_eq!(
+            warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "warning: line range `0-0` is out of a code block of 3 lines (1:1)",
+                "warning: line range `3-9` is out of a code block of 3 lines (1:1)",
+                "warning: line range `5-6` is out of a code block of 3 lines (1:1)",
+                "warning: line range `4-3` is out of a code block of 3 lines (1:1)",
+            ],
+            "should report ranges out of the code"
+        );
+    }
+
+    #[test]
+    fn visit_nested_code() {
+        let mut tree = hast::Node::Root(hast::Root {
+            children: vec![
+                code_block("a"),
+                hast::Node::Element(hast::Element {
+                    tag_name: "blockquote".into(),
+                    properties: vec![],
+                    children: vec![hast::Node::Element(hast::Element {
+                        tag_name: "li".into(),
+                        properties: vec![],
+                        children: vec![code_block("b")],
+                        position: None,
+                    })],
+                    position: None,
+                }),
+                hast::Node::MdxJsxElement(hast::MdxJsxElement {
+                    name: Some("Tab".into()),
+                    attributes: vec![],
+                    children: vec![code_block("c")],
+                    position: None,
+                }),
+            ],
+            position: None,
+        });
+
+        visit_code_mut(
+            &mut tree,
+            &config::HighlighterConfig::default(),
+            &LanguageRegistry::default(),
+            &mut vec![],
+        );
+
+        let children = tree.children().unwrap();
+        assert!(is_highlighted(&children[0]), "should highlight code at the root");
+        assert!(
+            is_highlighted(&children[1].children().unwrap()[0].children().unwrap()[0]),
+            "should highlight code in nested elements"
+        );
+        assert!(
+            is_highlighted(&children[2].children().unwrap()[0]),
+            "should highlight code in MDX JSX elements"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Warnings.
+
+use std::fmt;
+
+/// Problem in a code block that does not stop highlighting, such as a line
+/// range of its meta that is out of the code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warning {
+    /// What is wrong.
+    pub reason: String,
+    /// Line and column (1-indexed) of the code block, when known.
+    pub place: Option<(usize, usize)>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning: {}", self.reason)?;
+        if let Some((line, column)) = self.place {
+            write!(f, " ({}:{})", line, column)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{line_kind::LineKind, token_kind::TokenKind};
+
+/// Class names added to highlighted code.
+///
+/// Every class name starts with `prefix`, unless it is overridden.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase", default))]
+pub struct ClassNames {
+    /// Prefix of class names (default: `"hl"`).
+    pub prefix: String,
+    /// Class names of kinds of tokens, instead of the prefixed ones (default:
+    /// `vec![]`).
+    pub tokens: Vec<(TokenKind, String)>,
+    /// Class name of lines highlighted in the meta of a code fence, instead
+    /// of the prefixed one (default: `None`).
+    pub highlighted_line: Option<String>,
+}
+
+impl Default for ClassNames {
+    fn default() -> Self {
+        Self {
+            prefix: "hl".into(),
+            tokens: vec![],
+            highlighted_line: None,
+        }
+    }
+}
+
+impl ClassNames {
+    /// Class name of code blocks (`hl`).
+    pub fn code(&self) -> String {
+        self.prefix.clone()
+    }
+
+    /// Class name of lines (`hl-l`).
+    pub fn line(&self) -> String {
+        format!("{}-l", self.prefix)
+    }
+
+    /// Class name of highlighted lines (`hl-l-hl`).
+    pub fn highlighted_line(&self) -> String {
+        self.highlighted_line
+            .clone()
+            .unwrap_or_else(|| format!("{}-l-hl", self.prefix))
+    }
+
+    /// Class name of lines of `kind` (`hl-l-add`, `hl-l-del`).
+    pub fn line_kind(&self, kind: LineKind) -> String {
+        format!("{}-l-{}", self.prefix, kind.class_suffix())
+    }
+
+    /// Class name of the markers before the code of lines (`hl-mk`).
+    pub fn line_marker(&self) -> String {
+        format!("{}-mk", self.prefix)
+    }
+
+    /// Class name of tokens of `kind` (`hl-k`, `hl-s`, ...).
+    pub fn token(&self, kind: TokenKind) -> String {
+        self.tokens
+            .iter()
+            .find(|(token_kind, _)| *token_kind == kind)
+            .map(|(_, class_name)| class_name.clone())
+            .unwrap_or_else(|| format!("{}-{}", self.prefix, kind.class_suffix()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        CODE_CLASS_NAME, COMMENT_CLASS_NAME, HIGHLIGHTED_LINE_CLASS_NAME, KEYWORD_CLASS_NAME,
+        LINE_CLASS_NAME,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn defaults() {
+        let class_names = ClassNames::default();
+        assert_eq!(class_names.code(), CODE_CLASS_NAME, "should default to the constants (1)");
+        assert_eq!(class_names.line(), LINE_CLASS_NAME, "should default to the constants (2)");
+        assert_eq!(
+            class_names.highlighted_line(),
+            HIGHLIGHTED_LINE_CLASS_NAME,
+            "should default to the constants (3)"
+        );
+        assert_eq!(
+            class_names.token(TokenKind::Comment),
+            COMMENT_CLASS_NAME,
+            "should default to the constants (4)"
+        );
+        assert_eq!(
+            class_names.line_kind(LineKind::Added),
+            "hl-l-add",
+            "should default to the constants (5)"
+        );
+        assert_eq!(
+            class_names.line_kind(LineKind::Removed),
+            "hl-l-del",
+            "should default to the constants (6)"
+        );
+        assert_eq!(
+            class_names.line_marker(),
+            "hl-mk",
+            "should default to the constants (7)"
+        );
+    }
+
+    #[test]
+    fn prefix_and_overrides() {
+        let class_names = ClassNames {
+            prefix: "code".into(),
+            tokens: vec![(TokenKind::Keyword, "kw".into())],
+            highlighted_line: None,
+        };
+        assert_eq!(class_names.line(), "code-l", "should prefix lines");
+        assert_eq!(class_names.highlighted_line(), "code-l-hl", "should prefix highlighted lines");
+        assert_eq!(class_names.line_kind(LineKind::Added), "code-l-add", "should prefix kinds of lines");
+        assert_eq!(class_names.token(TokenKind::String), "code-s", "should prefix tokens");
+        assert_eq!(class_names.token(TokenKind::Keyword), "kw", "should support overrides");
+        assert_ne!(class_names.token(TokenKind::Keyword), KEYWORD_CLASS_NAME);
+    }
+}
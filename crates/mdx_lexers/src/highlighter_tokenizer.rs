@@ -1,10 +1,28 @@
+use std::collections::HashMap;
+
 use mdxjs::hast;
 
-use crate::constants::{LINE_CLASS_NAME, DATA_LINE_NUMBER_ATTR};
+use crate::constants::{DATA_HIGHLIGHTED_LINE_ATTR, DATA_LINE_NUMBER_ATTR};
+use crate::{class_names::ClassNames, line_kind::LineKind, theme::Theme, token_kind::TokenKind};
+
+/// Properties of a row, besides its tokens.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineProperties {
+    /// Kind of the line, added to the class name or style of the row.
+    pub kind: Option<LineKind>,
+    /// Text before the code of the line, which cannot be selected (example:
+    /// `+` in diffs).
+    pub marker: Option<String>,
+}
 
 pub struct HighlighterTokenizer {
     rows: Vec<hast::Node>,
     tokens: Vec<hast::Node>,
+    highlighted_lines: Vec<(usize, usize)>,
+    /// Properties of rows by line number, taken at the end of each line.
+    line_properties: HashMap<usize, LineProperties>,
+    class_names: ClassNames,
+    theme: Option<Theme>,
 }
 
 impl HighlighterTokenizer {
@@ -12,38 +30,162 @@ impl HighlighterTokenizer {
         Self {
             rows: vec![],
             tokens: vec![],
+            highlighted_lines: vec![],
+            line_properties: HashMap::new(),
+            class_names: ClassNames::default(),
+            theme: None,
+        }
+    }
+
+    /// Style tokens and lines inline with `theme` instead of class names.
+    pub fn inline_styles(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+    }
+
+    /// Mark the rows in `lines` (inclusive, 1-indexed ranges) as highlighted.
+    pub fn highlight_lines(&mut self, lines: Vec<(usize, usize)>) {
+        self.highlighted_lines = lines;
+    }
+
+    /// Number of the current row (1-indexed).
+    pub fn line(&self) -> usize {
+        self.rows.len() + 1
+    }
+
+    /// Mark the current row as a line of `kind`.
+    pub fn line_kind(&mut self, kind: LineKind) {
+        let line = self.line();
+        self.line_properties.entry(line).or_default().kind = Some(kind);
+    }
+
+    /// Set the properties of row `line` (1-indexed), which can be ahead of
+    /// the current row.
+    pub fn line_properties(&mut self, line: usize, properties: LineProperties) {
+        self.line_properties.insert(line, properties);
+    }
+
+    /// Use `class_names` for lines and tokens.
+    pub fn class_names(&mut self, class_names: ClassNames) {
+        self.class_names = class_names;
+    }
+
+    /// Tokenizer with the same class names and styles, without rows.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            class_names: self.class_names.clone(),
+            theme: self.theme.clone(),
+            ..Self::new()
         }
     }
 
+    /// Add highlighted nodes to the current row, such as the tokens of a row
+    /// of a fork.
+    pub(crate) fn add_nodes(&mut self, mut nodes: Vec<hast::Node>) {
+        self.tokens.append(&mut nodes);
+    }
+
     pub fn get_highlighted_code(self) -> Vec<hast::Node> {
         self.rows
     }
 
     pub fn end_of_line(&mut self) {
-        let row = hast::Node::Element(hast::Element {
-            tag_name: "div".into(),
-            properties: vec![
-                (
-                    "className".into(),
-                    hast::PropertyValue::String(LINE_CLASS_NAME.into()),
+        let line = self.line();
+        let highlighted = self
+            .highlighted_lines
+            .iter()
+            .any(|(start, end)| *start <= line && line <= *end);
+        let line_properties = self.line_properties.remove(&line).unwrap_or_default();
+        let line_kind = line_properties.kind;
+        let mut properties = vec![];
+        if let Some(theme) = &self.theme {
+            let background = if highlighted {
+                Some(theme.highlighted_line.as_str())
+            } else {
+                line_kind.map(|kind| theme.line_background(kind))
+            };
+            if let Some(background) = background {
+                properties.push((
+                    "style".into(),
+                    hast::PropertyValue::String(format!("background-color: {}", background)),
+                ));
+            }
+        } else {
+            let mut class_name = self.class_names.line();
+            if let Some(kind) = line_kind {
+                class_name = format!("{} {}", class_name, self.class_names.line_kind(kind));
+            }
+            if highlighted {
+                class_name = format!("{} {}", class_name, self.class_names.highlighted_line());
+            }
+            properties.push(("className".into(), hast::PropertyValue::String(class_name)));
+        }
+        properties.push((
+            DATA_LINE_NUMBER_ATTR.into(),
+            hast::PropertyValue::String(line.to_string()),
+        ));
+        if highlighted {
+            properties.push((
+                DATA_HIGHLIGHTED_LINE_ATTR.into(),
+                hast::PropertyValue::String("true".into()),
+            ));
+        }
+
+        let mut children = vec![];
+        if let Some(marker) = line_properties.marker {
+            let property = match &self.theme {
+                Some(theme) => (
+                    "style".into(),
+                    hast::PropertyValue::String(format!(
+                        "color: {}; user-select: none",
+                        theme.line_number
+                    )),
                 ),
-                (
-                    DATA_LINE_NUMBER_ATTR.into(),
-                    hast::PropertyValue::String((self.rows.len() + 1).to_string()),
+                None => (
+                    "className".into(),
+                    hast::PropertyValue::String(self.class_names.line_marker()),
                 ),
-            ],
-            children: self.tokens.clone(),
+            };
+            children.push(hast::Node::Element(hast::Element {
+                tag_name: "span".into(),
+                properties: vec![property],
+                children: vec![hast::Node::Text(hast::Text {
+                    value: marker,
+                    position: None,
+                })],
+                position: None,
+            }));
+        }
+        children.append(&mut self.tokens);
+
+        let row = hast::Node::Element(hast::Element {
+            tag_name: "div".into(),
+            properties,
+            children,
             position: None,
         });
         self.rows.push(row);
-
-        self.tokens = vec![];
     }
 
-    pub fn add_token(&mut self, text: String, class_name: String) {
+    pub fn add_token(&mut self, text: String, kind: TokenKind) {
+        let property = match &self.theme {
+            Some(theme) => {
+                let mut style = theme
+                    .token_style(kind)
+                    .map(|style| style.to_css())
+                    .unwrap_or_else(|| format!("color: {}", theme.foreground));
+                if !kind.is_selectable() {
+                    style.push_str("; user-select: none");
+                }
+                ("style".into(), hast::PropertyValue::String(style))
+            }
+            None => (
+                "className".into(),
+                hast::PropertyValue::String(self.class_names.token(kind)),
+            ),
+        };
         self.tokens.push(hast::Node::Element(hast::Element {
             tag_name: "span".into(),
-            properties: vec![("className".into(), hast::PropertyValue::String(class_name))],
+            properties: vec![property],
             children: vec![
                 (hast::Node::Text(hast::Text {
                     value: text,
@@ -54,6 +196,20 @@ impl HighlighterTokenizer {
         }));
     }
 
+    /// Add a token that may span several lines, closing a row at each line
+    /// ending.
+    pub fn add_multiline_token(&mut self, text: String, kind: TokenKind) {
+        let lines: Vec<&str> = text.split('\n').collect();
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                self.end_of_line();
+            }
+            if !line.is_empty() {
+                self.add_token((*line).into(), kind);
+            }
+        }
+    }
+
     pub fn add_text(&mut self, value: String) {
         if let Some(node) = self.tokens.last_mut() {
             if let hast::Node::Text(text) = node {
@@ -73,3 +229,184 @@ impl HighlighterTokenizer {
         }));
     }
 }
+
+impl Default for HighlighterTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn property<'a>(row: &'a hast::Node, name: &str) -> Option<&'a hast::PropertyValue> {
+        if let hast::Node::Element(element) = row {
+            element.properties.iter().find(|property| property.0 == name).map(|property| &property.1)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn highlighted_lines() {
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.highlight_lines(vec![(1, 1), (3, 4)]);
+        for _ in 0..5 {
+            tokens.end_of_line();
+        }
+        let rows = tokens.get_highlighted_code();
+
+        assert_eq!(
+            rows.iter()
+                .map(|row| property(row, DATA_HIGHLIGHTED_LINE_ATTR).is_some())
+                .collect::<Vec<_>>(),
+            vec![true, false, true, true, false],
+            "should mark the rows in the ranges"
+        );
+        assert_eq!(
+            property(&rows[0], "className"),
+            Some(&hast::PropertyValue::String("hl-l hl-l-hl".into())),
+            "should add the class name of highlighted lines"
+        );
+        assert_eq!(
+            property(&rows[1], "className"),
+            Some(&hast::PropertyValue::String("hl-l".into())),
+            "should not add it to other lines"
+        );
+    }
+
+    #[test]
+    fn class_names() {
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.class_names(ClassNames {
+            prefix: "code".into(),
+            tokens: vec![(TokenKind::Keyword, "kw".into())],
+            highlighted_line: None,
+        });
+        tokens.highlight_lines(vec![(1, 1)]);
+        tokens.add_token("if".into(), TokenKind::Keyword);
+        tokens.add_token("1".into(), TokenKind::Constant);
+        tokens.end_of_line();
+        let rows = tokens.get_highlighted_code();
+        let children = rows[0].children().unwrap();
+
+        assert_eq!(
+            property(&rows[0], "className"),
+            Some(&hast::PropertyValue::String("code-l code-l-hl".into())),
+            "should prefix line class names"
+        );
+        assert_eq!(
+            property(&children[0], "className"),
+            Some(&hast::PropertyValue::String("kw".into())),
+            "should support overridden token class names"
+        );
+        assert_eq!(
+            property(&children[1], "className"),
+            Some(&hast::PropertyValue::String("code-c".into())),
+            "should prefix token class names"
+        );
+    }
+
+    #[test]
+    fn inline_styles() {
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.inline_styles(Theme::dark());
+        tokens.highlight_lines(vec![(1, 1)]);
+        tokens.add_token("if".into(), TokenKind::Keyword);
+        tokens.end_of_line();
+        tokens.add_token("$ ".into(), TokenKind::Prompt);
+        tokens.end_of_line();
+        let rows = tokens.get_highlighted_code();
+
+        assert_eq!(
+            property(&rows[0].children().unwrap()[0], "style"),
+            Some(&hast::PropertyValue::String("color: #ff7b72".into())),
+            "should style tokens inline"
+        );
+        assert_eq!(
+            property(&rows[0], "style"),
+            Some(&hast::PropertyValue::String("background-color: #2f2a1e".into())),
+            "should style highlighted lines inline"
+        );
+        assert_eq!(
+            property(&rows[1].children().unwrap()[0], "style"),
+            Some(&hast::PropertyValue::String(
+                "color: #8b949e; font-weight: bold; user-select: none".into()
+            )),
+            "should not let prompts be selected"
+        );
+        assert_eq!(property(&rows[1], "style"), None, "should not style other lines");
+        assert_eq!(property(&rows[1], "className"), None, "should not add class names");
+    }
+
+    #[test]
+    fn line_kinds() {
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.highlight_lines(vec![(2, 2)]);
+        tokens.line_kind(LineKind::Added);
+        tokens.end_of_line();
+        tokens.line_kind(LineKind::Removed);
+        tokens.end_of_line();
+        tokens.end_of_line();
+        let rows = tokens.get_highlighted_code();
+
+        assert_eq!(
+            rows.iter().map(|row| property(row, "className")).collect::<Vec<_>>(),
+            vec![
+                Some(&hast::PropertyValue::String("hl-l hl-l-add".into())),
+                Some(&hast::PropertyValue::String("hl-l hl-l-del hl-l-hl".into())),
+                Some(&hast::PropertyValue::String("hl-l".into())),
+            ],
+            "should add the class names of kinds of lines to their row"
+        );
+
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.inline_styles(Theme::light());
+        tokens.line_kind(LineKind::Removed);
+        tokens.end_of_line();
+        let rows = tokens.get_highlighted_code();
+
+        assert_eq!(
+            property(&rows[0], "style"),
+            Some(&hast::PropertyValue::String("background-color: #ffebe9".into())),
+            "should style kinds of lines inline"
+        );
+    }
+
+    #[test]
+    fn line_properties() {
+        let mut tokens = HighlighterTokenizer::new();
+        tokens.line_properties(
+            2,
+            LineProperties {
+                kind: Some(LineKind::Added),
+                marker: Some("+".into()),
+            },
+        );
+        tokens.add_text("a".into());
+        tokens.end_of_line();
+        assert_eq!(tokens.line(), 2, "should count rows");
+        tokens.add_text("b".into());
+        tokens.end_of_line();
+        let rows = tokens.get_highlighted_code();
+
+        assert_eq!(
+            property(&rows[0], "className"),
+            Some(&hast::PropertyValue::String("hl-l".into())),
+            "should not add properties to other rows"
+        );
+        assert_eq!(
+            property(&rows[1], "className"),
+            Some(&hast::PropertyValue::String("hl-l hl-l-add".into())),
+            "should add properties set ahead of a row"
+        );
+        assert_eq!(
+            property(&rows[1].children().unwrap()[0], "className"),
+            Some(&hast::PropertyValue::String("hl-mk".into())),
+            "should add markers before the tokens of a row"
+        );
+        assert_eq!(rows[1].to_string(), "+b", "should keep the text of markers");
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, registry::LanguageRegistry};
+
+/// A lexer for one language.
+///
+/// Lexers push the tokens of the code into a [`HighlighterTokenizer`][],
+/// which groups them into rows. The last row is closed by the caller, so a
+/// lexer can also run on a slice of a bigger input: a lexer can hand part of
+/// its input, such as the content of a `<script>`, to another lexer with the
+/// same tokenizer, so the tokens of both end up in the same rows.
+///
+/// Tokens hold the raw source text: escaping is left to whatever renders the
+/// tree. Every character of the input must end up in exactly one token or
+/// text, so that joining the text of the rows with line endings gives back
+/// the input.
+pub trait Lexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer);
+
+    /// Like [`lex`][Self::lex], with the registry that is highlighting the
+    /// code, for lexers that hand code in other languages (such as fenced
+    /// code in Markdown) to the lexers registered for them.
+    fn lex_with(
+        &self,
+        input: Vec<char>,
+        tokens: &mut HighlighterTokenizer,
+        _registry: &LanguageRegistry,
+    ) {
+        self.lex(input, tokens);
+    }
+}
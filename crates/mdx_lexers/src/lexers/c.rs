@@ -0,0 +1,128 @@
+//! Lexer for C.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "alignas",
+        "alignof",
+        "auto",
+        "break",
+        "case",
+        "const",
+        "constexpr",
+        "continue",
+        "default",
+        "do",
+        "else",
+        "enum",
+        "extern",
+        "for",
+        "goto",
+        "if",
+        "inline",
+        "register",
+        "restrict",
+        "return",
+        "sizeof",
+        "static",
+        "static_assert",
+        "struct",
+        "switch",
+        "thread_local",
+        "typedef",
+        "typeof",
+        "union",
+        "volatile",
+        "while",
+        "_Alignas",
+        "_Alignof",
+        "_Atomic",
+        "_Generic",
+        "_Noreturn",
+        "_Static_assert",
+        "_Thread_local",
+    ],
+    types: &[
+        "bool",
+        "char",
+        "double",
+        "float",
+        "int",
+        "long",
+        "short",
+        "signed",
+        "unsigned",
+        "void",
+        "int8_t",
+        "int16_t",
+        "int32_t",
+        "int64_t",
+        "uint8_t",
+        "uint16_t",
+        "uint32_t",
+        "uint64_t",
+        "size_t",
+        "ssize_t",
+        "ptrdiff_t",
+        "intptr_t",
+        "uintptr_t",
+        "wchar_t",
+        "FILE",
+        "_Bool",
+        "_Complex",
+    ],
+    constants: &["true", "false", "NULL", "nullptr"],
+    capitalized_types: false,
+    nested_comments: false,
+    strings: &[StringSyntax::Double, StringSyntax::Char],
+    string_prefixes: &["L", "u8", "u", "U"],
+    annotations: AnnotationSyntax::None,
+    preprocessor: true,
+};
+
+pub struct CLexer;
+
+impl lexer::Lexer for CLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn c() {
+        let rows = highlight(
+            "#include <stdio.h>\n#define MAX 10\n\nint main(void) {\n  char c = '\\n';\n  printf(\"%d\\n\", MAX);\n  return 0;\n}",
+            "c",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["#include", "#define"],
+            "should highlight preprocessor directives"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["<stdio.h>", "'\\n'", "\"%d\\n\""],
+            "should highlight header names, chars and strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["int", "void", "char"],
+            "should highlight types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["main", "printf"],
+            "should highlight functions"
+        );
+    }
+}
@@ -0,0 +1,544 @@
+//! Base of lexers for languages with C-like comments, strings, numbers and
+//! braces.
+//!
+//! Each language describes its words and syntax in a `CLikeSyntax`, which
+//! `tokenize` follows.
+
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexers, token::Token};
+
+/// Kind of string literal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StringSyntax {
+    /// `"a\n"`, on one line.
+    Double,
+    /// `'a'`, a character.
+    Char,
+    /// `"""a"""`, a text block which can span lines.
+    TripleDouble,
+    /// `` `a` ``, a raw string which can span lines (Go).
+    Backtick,
+    /// `@"a""b"`, a verbatim string which can span lines (C#).
+    Verbatim,
+    /// `R"x(a)x"`, a raw string with a delimiter which can span lines (C++).
+    DelimitedRaw,
+    /// `#"a"#`, a raw string between hashes (Swift).
+    Hashed,
+}
+
+/// Syntax of annotations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnnotationSyntax {
+    None,
+    /// `@Override`, `@file:JvmName("A")`.
+    At,
+    /// `[Serializable]` before declarations (C#).
+    Brackets,
+}
+
+/// Words and syntax of a C-like language.
+#[derive(Clone, Copy, Debug)]
+pub struct CLikeSyntax {
+    pub keywords: &'static [&'static str],
+    /// Built-in types (`int`, `string`).
+    pub types: &'static [&'static str],
+    /// Built-in constants (`true`, `nil`).
+    pub constants: &'static [&'static str],
+    /// Whether capitalized identifiers are types (`String`), which is the
+    /// convention in Java but not in Go.
+    pub capitalized_types: bool,
+    /// Whether block comments can nest (`/* /* a */ */`).
+    pub nested_comments: bool,
+    pub strings: &'static [StringSyntax],
+    /// Prefixes of strings (`L"a"`, `$"a"`, `u8R"x(a)x"`).
+    pub string_prefixes: &'static [&'static str],
+    pub annotations: AnnotationSyntax,
+    /// Whether lines starting with `#` are preprocessor directives
+    /// (`#include <a.h>`, `#region`).
+    pub preprocessor: bool,
+}
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    syntax: &'static CLikeSyntax,
+}
+
+fn is_letter(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>, syntax: &'static CLikeSyntax) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            syntax,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    fn starts_with(&self, position: usize, value: &str) -> bool {
+        (position..)
+            .zip(value.chars())
+            .all(|(index, ch)| index < self.input.len() && self.input[index] == ch)
+    }
+
+    fn has_string(&self, string: StringSyntax) -> bool {
+        self.syntax.strings.contains(&string)
+    }
+
+    /// Last character before `position` that is not whitespace, if any.
+    fn previous_char(&self, position: usize) -> Option<char> {
+        self.input[..position]
+            .iter()
+            .rev()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+    }
+
+    /// Next character after `position` that is not whitespace.
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Whether `position` is the first character of its line that is not
+    /// whitespace.
+    fn is_line_start(&self, position: usize) -> bool {
+        self.input[..position]
+            .iter()
+            .rev()
+            .find(|ch| !matches!(ch, ' ' | '\t'))
+            .map(|ch| *ch == '\n')
+            .unwrap_or(true)
+    }
+
+    /// Move to `position` and return the text from `start`.
+    fn read_to(&mut self, start: usize, position: usize) -> Vec<char> {
+        let position = position.min(self.input.len());
+        self.read_position = position;
+        self.read_char();
+        self.input[start..position].to_vec()
+    }
+
+    /// Position after the end of the string of `string` syntax starting at
+    /// `position` (after its prefix).
+    fn string_end(&self, string: StringSyntax, position: usize) -> usize {
+        let len = self.input.len();
+        let find = |from: usize, close: &str, escapes: bool, lines: bool| -> usize {
+            let mut index = from;
+            while index < len {
+                if escapes && self.input[index] == '\\' {
+                    index += 2;
+                    continue;
+                }
+                if !lines && self.input[index] == '\n' {
+                    return index;
+                }
+                if self.starts_with(index, close) {
+                    return index + close.chars().count();
+                }
+                index += 1;
+            }
+            len
+        };
+        match string {
+            StringSyntax::Double => find(position + 1, "\"", true, false),
+            StringSyntax::Char => find(position + 1, "'", true, false),
+            StringSyntax::TripleDouble => find(position + 3, "\"\"\"", true, true),
+            StringSyntax::Backtick => find(position + 1, "`", false, true),
+            StringSyntax::Verbatim => {
+                // `""` is an escaped quote
+                let mut index = position + 1;
+                while index < len {
+                    if self.input[index] == '"' {
+                        if index + 1 < len && self.input[index + 1] == '"' {
+                            index += 2;
+                            continue;
+                        }
+                        return index + 1;
+                    }
+                    index += 1;
+                }
+                len
+            }
+            StringSyntax::DelimitedRaw => {
+                let start = position + 1;
+                let mut open = start;
+                while open < len && !matches!(self.input[open], '(' | '"' | '\n') {
+                    open += 1;
+                }
+                let delimiter: String = self.input[start..open.min(len)].iter().collect();
+                find(open + 1, &format!("){}\"", delimiter), false, true)
+            }
+            StringSyntax::Hashed => {
+                let hashes = self.input[position..]
+                    .iter()
+                    .take_while(|ch| **ch == '#')
+                    .count();
+                let close = format!("\"{}", "#".repeat(hashes));
+                find(position + hashes + 1, &close, false, true)
+            }
+        }
+    }
+
+    /// String starting at the current position, as its syntax and the length
+    /// of its prefix.
+    fn string_start(&self) -> Option<(StringSyntax, usize)> {
+        let prefix = self
+            .syntax
+            .string_prefixes
+            .iter()
+            .filter(|prefix| self.starts_with(self.position, prefix))
+            .map(|prefix| prefix.chars().count())
+            .max()
+            .unwrap_or(0);
+        let start = self.position + prefix;
+        let ch = if start < self.input.len() {
+            self.input[start]
+        } else {
+            '\0'
+        };
+        let prefix_ends_with = |ch: char| prefix > 0 && self.input[start - 1] == ch;
+
+        let string = if ch == '"'
+            && prefix_ends_with('@')
+            && self.has_string(StringSyntax::Verbatim)
+        {
+            StringSyntax::Verbatim
+        } else if ch == '"' && prefix_ends_with('R') && self.has_string(StringSyntax::DelimitedRaw)
+        {
+            StringSyntax::DelimitedRaw
+        } else if self.starts_with(start, "\"\"\"") && self.has_string(StringSyntax::TripleDouble) {
+            StringSyntax::TripleDouble
+        } else if ch == '"' && self.has_string(StringSyntax::Double) {
+            StringSyntax::Double
+        } else if ch == '\'' && self.has_string(StringSyntax::Char) {
+            StringSyntax::Char
+        } else if ch == '`' && self.has_string(StringSyntax::Backtick) {
+            StringSyntax::Backtick
+        } else if ch == '#' && self.has_string(StringSyntax::Hashed) {
+            let hashes = self.input[start..]
+                .iter()
+                .take_while(|ch| **ch == '#')
+                .count();
+            if self.input.get(start + hashes) != Some(&'"') {
+                return None;
+            }
+            StringSyntax::Hashed
+        } else {
+            return None;
+        };
+        // Prefixes that are not followed by a string are identifiers
+        if prefix > 0 && !matches!(ch, '"' | '\'') {
+            return None;
+        }
+        Some((string, prefix))
+    }
+
+    fn read_block_comment(&mut self) -> Vec<char> {
+        let position = self.position;
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            if self.ch == '/' && self.peek_char(1) == '*' {
+                depth += 1;
+                self.read_char();
+            } else if self.ch == '*' && self.peek_char(1) == '/' {
+                depth -= 1;
+                self.read_char();
+                if depth == 0 || !self.syntax.nested_comments {
+                    self.read_char();
+                    break;
+                }
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        let is_hex = self.ch == '0' && matches!(self.peek_char(1), 'x' | 'X');
+        while self.position < self.input.len() {
+            if matches!(self.ch, 'e' | 'E' | 'p' | 'P')
+                && matches!(self.peek_char(1), '+' | '-')
+                && (!is_hex || matches!(self.ch, 'p' | 'P'))
+            {
+                self.read_char();
+            } else if self.ch == '\'' && self.peek_char(1).is_ascii_alphanumeric() {
+                // Digit separators (`1'000`)
+            } else if self.ch == '.' {
+                if !self.peek_char(1).is_ascii_digit() {
+                    break;
+                }
+            } else if !(self.ch.is_alphanumeric() || self.ch == '_') {
+                break;
+            }
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    fn read_identifier(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && (is_letter(self.ch) || self.ch.is_numeric()) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether the `[` at `position` starts an attribute (`[Serializable]`).
+    fn is_attribute_start(&self, position: usize) -> bool {
+        self.syntax.annotations == AnnotationSyntax::Brackets
+            && (self.is_line_start(position)
+                || matches!(
+                    self.previous_char(position),
+                    None | Some(';' | '{' | '}' | ']')
+                ))
+            && self
+                .input
+                .get(position + 1)
+                .map(|ch| is_letter(*ch))
+                .unwrap_or(false)
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+        if self.ch == '/' && self.peek_char(1) == '/' {
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            return Token::COMMENT(self.input[position..self.position].to_vec());
+        }
+        if self.ch == '/' && self.peek_char(1) == '*' {
+            return Token::COMMENT(self.read_block_comment());
+        }
+        if let Some((string, prefix)) = self.string_start() {
+            let end = self.string_end(string, position + prefix);
+            return Token::STRING(self.read_to(position, end));
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '#' if self.syntax.preprocessor && self.is_line_start(position) => {
+                self.read_char();
+                while matches!(self.ch, ' ' | '\t') {
+                    self.read_char();
+                }
+                let directive: String = self.read_identifier().iter().collect();
+                // Directives followed by free text (`#region Main`, `#pragma once`)
+                if matches!(
+                    &directive[..],
+                    "region" | "endregion" | "pragma" | "error" | "warning"
+                ) {
+                    while self.position < self.input.len() && self.ch != '\n' {
+                        self.read_char();
+                    }
+                }
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            '<' if self.syntax.preprocessor && self.is_header_name(position) => {
+                let end = self.input[position..]
+                    .iter()
+                    .position(|ch| *ch == '>')
+                    .map(|end| position + end + 1)
+                    .unwrap_or(self.input.len());
+                Token::STRING(self.read_to(position, end))
+            }
+            '@' if self.syntax.annotations == AnnotationSyntax::At
+                && is_letter(self.peek_char(1)) =>
+            {
+                self.read_char();
+                self.read_identifier();
+                // Use-site targets (`@file:JvmName`)
+                if self.ch == ':' && is_letter(self.peek_char(1)) {
+                    self.read_char();
+                    self.read_identifier();
+                }
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            ch if ch.is_ascii_digit() || (ch == '.' && self.peek_char(1).is_ascii_digit()) => {
+                Token::INT(self.read_number())
+            }
+            ch if is_letter(ch) => {
+                let identifier = self.read_identifier();
+                self.identifier_token(position, identifier)
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+
+    /// Whether the `<` at `position` starts a header name (`#include <a.h>`).
+    fn is_header_name(&self, position: usize) -> bool {
+        let mut start = position;
+        while start > 0 && self.input[start - 1] != '\n' {
+            start -= 1;
+        }
+        let line: String = self.input[start..position].iter().collect();
+        let directive = line.trim().trim_start_matches('#').trim();
+        directive == "include" || directive == "import"
+    }
+
+    fn identifier_token(&self, position: usize, identifier: Vec<char>) -> Token {
+        let name: String = identifier.iter().collect();
+        let in_attribute = self.syntax.annotations == AnnotationSyntax::Brackets
+            && position > 0
+            && self.input[position - 1] == '['
+            && self.is_attribute_start(position - 1);
+
+        if in_attribute {
+            Token::META(identifier)
+        } else if self.syntax.constants.contains(&&name[..]) {
+            Token::CONSTANT(identifier)
+        } else if self.syntax.keywords.contains(&&name[..]) {
+            Token::KEYWORD(identifier)
+        } else if self.syntax.types.contains(&&name[..]) {
+            Token::TYPE(identifier)
+        } else if self.next_char(self.position) == '(' {
+            Token::ENTITY(identifier)
+        } else if self.syntax.capitalized_types
+            && name.starts_with(|ch: char| ch.is_uppercase())
+            && name.chars().any(|ch| ch.is_lowercase())
+        {
+            Token::TYPE(identifier)
+        } else {
+            Token::IDENT(identifier)
+        }
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer, syntax: &'static CLikeSyntax) {
+    let mut l = Lexer::new(input, syntax);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer, test_utils::with_class, LanguageRegistry};
+    use pretty_assertions::assert_eq;
+
+    const SYNTAX: CLikeSyntax = CLikeSyntax {
+        keywords: &["if", "return"],
+        types: &["int"],
+        constants: &["true"],
+        capitalized_types: true,
+        nested_comments: true,
+        strings: &[
+            StringSyntax::Double,
+            StringSyntax::Char,
+            StringSyntax::Verbatim,
+        ],
+        string_prefixes: &["L", "@"],
+        annotations: AnnotationSyntax::At,
+        preprocessor: true,
+    };
+
+    struct TestLexer;
+
+    impl lexer::Lexer for TestLexer {
+        fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+            tokenize(input, tokens, &SYNTAX);
+        }
+    }
+
+    fn highlight(code: &str) -> Vec<mdxjs::hast::Node> {
+        let mut registry = LanguageRegistry::new();
+        registry.register(&["test"], TestLexer);
+        registry.highlight(code.chars().collect(), "test")
+    }
+
+    #[test]
+    fn syntax() {
+        let rows = highlight(
+            "#include <a.h>\n@Deprecated /* a /* b */ c */\nint f(Map m) {\n  if (true) return L\"d\\\"\" + 'e' + @\"f\"\"\ng\" + 0x1Fu + 1'000 + 1.5e-3f;\n}",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["#include", "@Deprecated"],
+            "should highlight directives and annotations"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s")[0],
+            "<a.h>",
+            "should highlight header names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["/* a /* b */ c */"],
+            "should highlight nested comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["int", "Map"],
+            "should highlight types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["f"],
+            "should highlight functions"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["if", "return"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s")[1..],
+            ["L\"d\\\"\"", "'e'", "@\"f\"\"", "g\""],
+            "should highlight strings with prefixes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["true", "0x1Fu", "1'000", "1.5e-3f"],
+            "should highlight constants and numbers"
+        );
+    }
+}
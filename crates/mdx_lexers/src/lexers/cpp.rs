@@ -0,0 +1,151 @@
+//! Lexer for C++.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "alignas",
+        "alignof",
+        "auto",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "co_await",
+        "co_return",
+        "co_yield",
+        "concept",
+        "const",
+        "const_cast",
+        "consteval",
+        "constexpr",
+        "constinit",
+        "continue",
+        "decltype",
+        "default",
+        "delete",
+        "do",
+        "dynamic_cast",
+        "else",
+        "enum",
+        "explicit",
+        "export",
+        "extern",
+        "final",
+        "for",
+        "friend",
+        "goto",
+        "if",
+        "inline",
+        "mutable",
+        "namespace",
+        "new",
+        "noexcept",
+        "operator",
+        "override",
+        "private",
+        "protected",
+        "public",
+        "register",
+        "reinterpret_cast",
+        "requires",
+        "return",
+        "sizeof",
+        "static",
+        "static_assert",
+        "static_cast",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "thread_local",
+        "throw",
+        "try",
+        "typedef",
+        "typeid",
+        "typename",
+        "union",
+        "using",
+        "virtual",
+        "volatile",
+        "while",
+    ],
+    types: &[
+        "bool",
+        "char",
+        "char8_t",
+        "char16_t",
+        "char32_t",
+        "double",
+        "float",
+        "int",
+        "long",
+        "short",
+        "signed",
+        "unsigned",
+        "void",
+        "wchar_t",
+        "int8_t",
+        "int16_t",
+        "int32_t",
+        "int64_t",
+        "uint8_t",
+        "uint16_t",
+        "uint32_t",
+        "uint64_t",
+        "size_t",
+        "ptrdiff_t",
+    ],
+    constants: &["true", "false", "nullptr", "NULL"],
+    capitalized_types: false,
+    nested_comments: false,
+    strings: &[
+        StringSyntax::DelimitedRaw,
+        StringSyntax::Double,
+        StringSyntax::Char,
+    ],
+    string_prefixes: &["L", "u8", "u", "U", "R", "LR", "u8R", "uR", "UR"],
+    annotations: AnnotationSyntax::None,
+    preprocessor: true,
+};
+
+pub struct CppLexer;
+
+impl lexer::Lexer for CppLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cpp() {
+        let rows = highlight(
+            "#include <vector>\ntemplate <typename T>\nauto s = R\"x(a \")\"\nb)x\" + u8\"c\";\nint n = 1'000'000;",
+            "c++",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["template", "typename", "auto"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["<vector>", "R\"x(a \")\"", "b)x\"", "u8\"c\""],
+            "should highlight raw strings with delimiters and prefixed strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1'000'000"],
+            "should highlight digit separators"
+        );
+    }
+}
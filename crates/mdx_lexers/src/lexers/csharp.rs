@@ -0,0 +1,135 @@
+//! Lexer for C#.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "base",
+        "break",
+        "case",
+        "catch",
+        "checked",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delegate",
+        "do",
+        "else",
+        "enum",
+        "event",
+        "explicit",
+        "extern",
+        "finally",
+        "fixed",
+        "for",
+        "foreach",
+        "get",
+        "goto",
+        "if",
+        "implicit",
+        "in",
+        "init",
+        "interface",
+        "internal",
+        "is",
+        "lock",
+        "namespace",
+        "new",
+        "operator",
+        "out",
+        "override",
+        "params",
+        "partial",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "record",
+        "ref",
+        "required",
+        "return",
+        "sealed",
+        "set",
+        "sizeof",
+        "stackalloc",
+        "static",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "typeof",
+        "unchecked",
+        "unsafe",
+        "using",
+        "var",
+        "virtual",
+        "volatile",
+        "when",
+        "where",
+        "while",
+        "yield",
+    ],
+    types: &[
+        "bool", "byte", "char", "decimal", "double", "dynamic", "float", "int", "long", "nint",
+        "nuint", "object", "sbyte", "short", "string", "uint", "ulong", "ushort", "void",
+    ],
+    constants: &["true", "false", "null"],
+    capitalized_types: true,
+    nested_comments: false,
+    strings: &[
+        StringSyntax::Verbatim,
+        StringSyntax::TripleDouble,
+        StringSyntax::Double,
+        StringSyntax::Char,
+    ],
+    string_prefixes: &["$", "@", "$@", "@$"],
+    annotations: AnnotationSyntax::Brackets,
+    preprocessor: true,
+};
+
+pub struct CsharpLexer;
+
+impl lexer::Lexer for CsharpLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn csharp() {
+        let rows = highlight(
+            "#region Main\n[Serializable]\npublic class Point {\n  string p = @\"C:\\\"\"x\"\"\";\n  string s = $\"{p}\";\n  string e = @\"\", q = @\"\"\"a\";\n  int[] a = new int[2];\n}\n#endregion",
+            "cs",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["#region Main", "Serializable", "#endregion"],
+            "should highlight directives and attributes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["@\"C:\\\"\"x\"\"\"", "$\"{p}\"", "@\"\"", "@\"\"\"a\""],
+            "should highlight verbatim and interpolated strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["Point", "string", "string", "string", "int", "int"],
+            "should highlight types without treating indexing as attributes"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+/// Part of a stylesheet the lexer is in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Context {
+    /// Start of a rule or declaration, not known yet.
+    Statement,
+    /// Selector of a rule (`a:hover > .b`).
+    Selector,
+    /// Name of a declaration (`color`).
+    Property,
+    /// Value of a declaration (`1px solid red`).
+    Value,
+    /// Prelude of an at-rule (`@media (min-width: 1px)`).
+    AtRule,
+}
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Whether `//` starts a comment (SCSS, Less).
+    pub line_comments: bool,
+    context: Context,
+    /// Whether the lexer is in an attribute selector (`[type="text"]`).
+    attribute_selector: bool,
+}
+
+fn is_name_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_' || ch == '-'
+}
+
+fn is_name_char(ch: char) -> bool {
+    is_name_start(ch) || ch.is_numeric()
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            line_comments: false,
+            context: Context::Statement,
+            attribute_selector: false,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Whether a name (`a`, `-webkit-x`, `--x`) starts `offset` characters
+    /// after the current position.
+    fn is_name_at(&self, offset: usize) -> bool {
+        let ch = self.peek_char(offset);
+        if ch == '-' {
+            is_name_start(self.peek_char(offset + 1)) || self.peek_char(offset + 1) == '-'
+        } else {
+            is_name_start(ch)
+        }
+    }
+
+    fn read_name(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_name_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    fn read_string(&mut self) -> Vec<char> {
+        let position = self.position;
+        let quote = self.ch;
+        self.read_char();
+        while self.position < self.input.len() && self.ch != quote && self.ch != '\n' {
+            if self.ch == '\\' {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        if self.ch == quote {
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read a number with its unit (`1.5em`, `50%`, `-2px`).
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        if matches!(self.ch, '-' | '+') {
+            self.read_char();
+        }
+        while self.position < self.input.len() && self.ch.is_numeric() {
+            self.read_char();
+        }
+        if self.ch == '.' && self.peek_char(1).is_numeric() {
+            self.read_char();
+            while self.position < self.input.len() && self.ch.is_numeric() {
+                self.read_char();
+            }
+        }
+        if self.ch == '%' {
+            self.read_char();
+        } else if self.ch.is_alphabetic() {
+            self.read_name();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether a number starts at the current position.
+    fn is_number_start(&self) -> bool {
+        let (ch, next) = if matches!(self.ch, '-' | '+') {
+            (self.peek_char(1), self.peek_char(2))
+        } else {
+            (self.ch, self.peek_char(1))
+        };
+        if matches!(self.ch, '-' | '+')
+            && self.position > 0
+            && (is_name_char(self.input[self.position - 1]) || self.input[self.position - 1] == ')')
+        {
+            return false;
+        }
+        ch.is_numeric() || (ch == '.' && next.is_numeric())
+    }
+
+    /// Read an interpolation (`#{$x}`, `@{x}`) up to its closing brace.
+    fn read_interpolation(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && self.ch != '}' && self.ch != '\n' {
+            self.read_char();
+        }
+        if self.ch == '}' {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Context of the statement at the current position: a rule if a `{`
+    /// comes before the end of the statement, a declaration otherwise.
+    fn statement_context(&self) -> Context {
+        if self.ch == '@' && !(self.is_name_at(1) && self.is_variable_at(1)) {
+            return Context::AtRule;
+        }
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut index = self.position;
+        while index < self.input.len() {
+            let ch = self.input[index];
+            let next = self.input.get(index + 1).copied().unwrap_or('\0');
+            if let Some(quote_ch) = quote {
+                if ch == '\\' {
+                    index += 1;
+                } else if ch == quote_ch {
+                    quote = None;
+                }
+            } else {
+                match ch {
+                    '"' | '\'' => quote = Some(ch),
+                    '/' if next == '*' => {
+                        index = self.input[index..]
+                            .windows(2)
+                            .position(|pair| pair == ['*', '/'])
+                            .map_or(self.input.len(), |end| index + end + 1);
+                    }
+                    '/' if next == '/' && self.line_comments => {
+                        while index < self.input.len() && self.input[index] != '\n' {
+                            index += 1;
+                        }
+                    }
+                    '#' | '@' if next == '{' => index += 1,
+                    '(' | '[' => depth += 1,
+                    ')' | ']' => depth -= 1,
+                    '{' if depth <= 0 => return Context::Selector,
+                    ';' | '}' if depth <= 0 => return Context::Property,
+                    _ => {}
+                }
+            }
+            index += 1;
+        }
+        Context::Property
+    }
+
+    /// Whether the name `offset` characters after the current position is
+    /// directly followed by `:`, as in Less variables (`@x: 1`).
+    fn is_variable_at(&self, offset: usize) -> bool {
+        let mut offset = offset;
+        while is_name_char(self.peek_char(offset)) {
+            offset += 1;
+        }
+        self.peek_char(offset) == ':'
+    }
+
+    /// Last word before `position`, skipping whitespace.
+    fn previous_word(&self, position: usize) -> String {
+        let mut end = position;
+        while end > 0 && self.input[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        let mut start = end;
+        while start > 0 && is_name_char(self.input[start - 1]) {
+            start -= 1;
+        }
+        self.input[start..end].iter().collect()
+    }
+
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| **ch != ' ' && **ch != '\t')
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+        if self.ch == '/' && self.peek_char(1) == '*' {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len() && !(self.ch == '*' && self.peek_char(1) == '/')
+            {
+                self.read_char();
+            }
+            self.read_char();
+            self.read_char();
+            return Token::COMMENT(
+                self.input[position..self.position.min(self.input.len())].to_vec(),
+            );
+        }
+        if self.line_comments && self.ch == '/' && self.peek_char(1) == '/' {
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            return Token::COMMENT(self.input[position..self.position].to_vec());
+        }
+        if self.ch.is_whitespace() {
+            self.read_char();
+            return if self.input[position] == '\n' {
+                Token::ENDL('\n')
+            } else {
+                Token::CH(self.input[position])
+            };
+        }
+
+        if self.context == Context::Statement {
+            self.context = self.statement_context();
+        }
+
+        match self.ch {
+            '"' | '\'' => return Token::STRING(self.read_string()),
+            '#' | '@' if self.peek_char(1) == '{' => return Token::VAR(self.read_interpolation()),
+            '$' if self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                return Token::VAR(self.input[position..self.position].to_vec());
+            }
+            '{' | '}' | ';' => {
+                self.context = Context::Statement;
+                self.attribute_selector = false;
+                self.read_char();
+                return Token::CH(self.input[position]);
+            }
+            _ => {}
+        }
+
+        match self.context {
+            Context::Selector => self.next_selector_token(position),
+            Context::Property => self.next_property_token(position),
+            Context::Value | Context::AtRule | Context::Statement => {
+                self.next_value_token(position)
+            }
+        }
+    }
+
+    fn next_selector_token(&mut self, position: usize) -> Token {
+        match self.ch {
+            '.' | '#' | '%' if self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                Token::ENTITY(self.input[position..self.position].to_vec())
+            }
+            ':' if self.is_name_at(1) || (self.peek_char(1) == ':' && self.is_name_at(2)) => {
+                self.read_char();
+                if self.ch == ':' {
+                    self.read_char();
+                }
+                self.read_name();
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            '&' => {
+                self.read_char();
+                Token::VAR(vec!['&'])
+            }
+            '[' | ']' => {
+                self.attribute_selector = self.ch == '[';
+                self.read_char();
+                Token::CH(self.input[position])
+            }
+            ch if self.attribute_selector && is_name_start(ch) => {
+                let name = self.read_name();
+                if self.previous_char(position) == Some('[') {
+                    Token::ATTRIBUTE(name)
+                } else {
+                    Token::STRING(name)
+                }
+            }
+            _ if self.is_number_start() => Token::INT(self.read_number()),
+            _ if self.is_name_at(0) => Token::ENTITYTAG(self.read_name()),
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+
+    fn next_property_token(&mut self, position: usize) -> Token {
+        match self.ch {
+            ':' => {
+                self.context = Context::Value;
+                self.read_char();
+                Token::CH(':')
+            }
+            '@' if self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                Token::VAR(self.input[position..self.position].to_vec())
+            }
+            '.' | '#' if self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                Token::ENTITY(self.input[position..self.position].to_vec())
+            }
+            '-' if self.peek_char(1) == '-' => Token::VAR(self.read_name()),
+            _ if self.is_name_at(0) => Token::ATTRIBUTE(self.read_name()),
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+
+    fn next_value_token(&mut self, position: usize) -> Token {
+        // Unquoted URLs (`url(a/b.png)`)
+        if self.previous_char(position) == Some('(')
+            && self.previous_word(position.saturating_sub(1)) == "url"
+            && !matches!(self.ch, '"' | '\'' | ')')
+        {
+            while self.position < self.input.len() && self.ch != ')' && self.ch != '\n' {
+                self.read_char();
+            }
+            return Token::STRING(self.input[position..self.position].to_vec());
+        }
+
+        match self.ch {
+            '@' if self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                if self.context == Context::AtRule && position == self.statement_start(position) {
+                    Token::KEYWORD(self.input[position..self.position].to_vec())
+                } else {
+                    Token::VAR(self.input[position..self.position].to_vec())
+                }
+            }
+            '#' if self.peek_char(1).is_ascii_hexdigit() => {
+                self.read_char();
+                self.read_name();
+                Token::CONSTANT(self.input[position..self.position].to_vec())
+            }
+            '.' | '%' if self.context == Context::AtRule && self.is_name_at(1) => {
+                self.read_char();
+                self.read_name();
+                Token::ENTITY(self.input[position..self.position].to_vec())
+            }
+            '!' if self.peek_char(1).is_alphabetic() => {
+                self.read_char();
+                self.read_name();
+                Token::KEYWORD(self.input[position..self.position].to_vec())
+            }
+            '-' if self.peek_char(1) == '-' => Token::VAR(self.read_name()),
+            _ if self.is_number_start() => Token::INT(self.read_number()),
+            _ if self.is_name_at(0) => {
+                let name = self.read_name();
+                // Functions (`calc(`), mixins and animations (`@include a`)
+                if self.ch == '('
+                    || (self.context == Context::AtRule
+                        && matches!(
+                            &self.previous_word(position)[..],
+                            "include" | "mixin" | "function" | "keyframes"
+                        ))
+                {
+                    Token::ENTITY(name)
+                } else if self.context == Context::AtRule && self.next_char(self.position) == ':' {
+                    Token::ATTRIBUTE(name)
+                } else {
+                    Token::IDENT(name)
+                }
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+
+    /// Last character before `position` that is not whitespace.
+    fn previous_char(&self, position: usize) -> Option<char> {
+        self.input[..position.min(self.input.len())]
+            .iter()
+            .rev()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+    }
+
+    /// Position of the first character of the statement of `position`.
+    fn statement_start(&self, position: usize) -> usize {
+        let mut start = position;
+        while start > 0 && !matches!(self.input[start - 1], '{' | '}' | ';') {
+            start -= 1;
+        }
+        while start < position && self.input[start].is_whitespace() {
+            start += 1;
+        }
+        start
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer, line_comments: bool) {
+    let mut l = Lexer::new(input);
+    l.line_comments = line_comments;
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct CssLexer {
+    /// Whether `//` starts a comment (SCSS, Less).
+    pub line_comments: bool,
+}
+
+impl lexer::Lexer for CssLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.line_comments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn css() {
+        let rows = highlight(
+            "/* Buttons */\na.btn:hover, #main > input[type=\"text\"]::before {\n  --gap: 4px;\n  margin: -1.5em calc(var(--gap) * 2) !important;\n  color: #FF0000;\n  background: url(a/b.png);\n}",
+            "css",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["/* Buttons */"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["a", "input"],
+            "should highlight tag names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec![".btn", "#main", "calc", "var", "url"],
+            "should highlight classes, ids and functions"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec![":hover", "::before"],
+            "should highlight pseudo classes and elements"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["type", "margin", "color", "background"],
+            "should highlight attribute and property names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["--gap", "--gap"],
+            "should highlight custom properties"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["4px", "-1.5em", "2", "#FF0000"],
+            "should highlight numbers and colours"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"text\"", "a/b.png"],
+            "should highlight strings and URLs"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["!important"],
+            "should highlight `!important`"
+        );
+    }
+
+    #[test]
+    fn at_rules() {
+        let rows = highlight(
+            "@import 'a.css';\n@media screen and (min-width: 100px) {\n  .a { display: none }\n}",
+            "css",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["@import", "@media"],
+            "should highlight at-rules"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["min-width", "display"],
+            "should highlight media features"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec![".a"],
+            "should highlight nested rules"
+        );
+    }
+
+    #[test]
+    fn scss() {
+        let rows = highlight(
+            "// Theme\n$primary: #333 !default;\n@mixin rounded($r: 4px) { border-radius: $r; }\n.card {\n  &:hover { color: $primary; }\n  @include rounded(2px);\n  .title { width: 50%; }\n}",
+            "scss",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["// Theme"],
+            "should highlight line comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["$primary", "$r", "$r", "&", "$primary"],
+            "should highlight variables and parent selectors"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["!default", "@mixin", "@include"],
+            "should highlight at-rules"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["rounded", ".card", "rounded", ".title"],
+            "should highlight mixins and nested rules"
+        );
+
+        let rows = highlight("a { b: url(//c) }", "css");
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["//c"],
+            "should not support line comments in CSS"
+        );
+    }
+
+    #[test]
+    fn less() {
+        let rows = highlight(
+            "@color: #4d926f;\n.box {\n  color: @color;\n  .rounded(5px);\n}",
+            "less",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["@color", "@color"],
+            "should highlight variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec![".box", ".rounded"],
+            "should highlight mixin calls"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use mdxjs::hast;
+
+use crate::{
+    highlighter_tokenizer::{HighlighterTokenizer, LineProperties},
+    lexer, lexers,
+    line_kind::LineKind,
+    token::Token,
+};
+
+/// Kind of a line of a diff.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Line {
+    /// Line of the header of a file (`diff --git`, `--- a`, `+++ b`).
+    Header,
+    /// Header of a hunk (`@@ -1,2 +1,3 @@`).
+    Hunk,
+    Added,
+    Removed,
+    Context,
+    /// `\ No newline at end of file`.
+    Note,
+}
+
+/// Lines that start the header of a file, as written by `git diff`.
+const HEADERS: [&str; 13] = [
+    "diff ",
+    "index ",
+    "new file mode",
+    "deleted file mode",
+    "old mode",
+    "new mode",
+    "similarity index",
+    "dissimilarity index",
+    "rename from",
+    "rename to",
+    "copy from",
+    "copy to",
+    "Binary files",
+];
+
+/// Sorts the lines of a diff into kinds.
+pub struct Lexer {
+    /// Lines of the old and new file left in the current hunk, if in one.
+    hunk: Option<(usize, usize)>,
+    /// Whether the previous line was a `---` header.
+    after_old_header: bool,
+}
+
+/// Line counts of the old and new file in a hunk header (`@@ -1,2 +1 @@`).
+fn hunk_counts(line: &str) -> Option<(usize, usize)> {
+    let mut ranges = line.strip_prefix("@@ ")?.split(' ');
+    let count = |range: Option<&str>, sign: char| -> Option<usize> {
+        match range?.strip_prefix(sign)?.split_once(',') {
+            Some((_, count)) => count.parse().ok(),
+            None => Some(1),
+        }
+    };
+    Some((count(ranges.next(), '-')?, count(ranges.next(), '+')?))
+}
+
+impl Lexer {
+    pub fn new() -> Self {
+        Self {
+            hunk: None,
+            after_old_header: false,
+        }
+    }
+
+    /// Kind of `line`, where `next` is the line after it.
+    pub fn line(&mut self, line: &str, next: Option<&str>) -> Line {
+        let after_old_header = self.after_old_header;
+        self.after_old_header = false;
+
+        if line.starts_with("@@") {
+            // Hunks without valid counts last until the next header
+            self.hunk = Some(hunk_counts(line).unwrap_or((usize::MAX, usize::MAX)));
+            return Line::Hunk;
+        }
+        if line.starts_with('\\') {
+            return Line::Note;
+        }
+        if let Some((old, new)) = self.hunk {
+            let kind = match line.chars().next() {
+                Some('+') if new > 0 => Some((Line::Added, old, new - 1)),
+                Some('-') if old > 0 => Some((Line::Removed, old - 1, new)),
+                // Some tools strip the space of empty context lines
+                Some(' ') | None if old > 0 && new > 0 => Some((Line::Context, old - 1, new - 1)),
+                _ => None,
+            };
+            if let Some((kind, old, new)) = kind {
+                self.hunk = if old == 0 && new == 0 {
+                    None
+                } else {
+                    Some((old, new))
+                };
+                return kind;
+            }
+            self.hunk = None;
+        }
+
+        if HEADERS.iter().any(|header| line.starts_with(header))
+            || (line.starts_with("+++ ") && after_old_header)
+        {
+            return Line::Header;
+        }
+        if line.starts_with("--- ") && next.map(|next| next.starts_with("+++ ")).unwrap_or(false) {
+            self.after_old_header = true;
+            return Line::Header;
+        }
+        match line.chars().next() {
+            Some('+') => Line::Added,
+            Some('-') => Line::Removed,
+            _ => Line::Context,
+        }
+    }
+}
+
+impl Default for Lexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Line {
+    /// Whether the line is code of the old or new file.
+    fn is_code(self) -> bool {
+        matches!(self, Line::Added | Line::Removed | Line::Context)
+    }
+
+    fn kind(self) -> Option<LineKind> {
+        match self {
+            Line::Added => Some(LineKind::Added),
+            Line::Removed => Some(LineKind::Removed),
+            _ => None,
+        }
+    }
+}
+
+/// Kinds of `lines`.
+fn line_kinds(lines: &[&str]) -> Vec<Line> {
+    let mut l = Lexer::new();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| l.line(line, lines.get(index + 1).copied()))
+        .collect()
+}
+
+fn add_line(tokens: &mut HighlighterTokenizer, line: &str, kind: Line) {
+    if let Some(kind) = kind.kind() {
+        tokens.line_kind(kind);
+    }
+    match kind {
+        Line::Header => lexers::add_token(tokens, Token::META(line.chars().collect())),
+        Line::Hunk => {
+            // The range, and then the section heading (`@@ -1 +1 @@ fn a`)
+            let end = line[2..]
+                .find("@@")
+                .map(|end| end + 4)
+                .unwrap_or(line.len());
+            lexers::add_token(tokens, Token::ENTITY(line[..end].chars().collect()));
+            if end < line.len() {
+                tokens.add_text(line[end..].into());
+            }
+        }
+        Line::Added => lexers::add_token(tokens, Token::INSERTED(line.chars().collect())),
+        Line::Removed => lexers::add_token(tokens, Token::DELETED(line.chars().collect())),
+        Line::Note => lexers::add_token(tokens, Token::COMMENT(line.chars().collect())),
+        Line::Context if !line.is_empty() => tokens.add_text(line.into()),
+        Line::Context => {}
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let input: String = input.iter().collect();
+    let lines: Vec<&str> = input.split('\n').collect();
+    for (index, (line, kind)) in lines.iter().zip(line_kinds(&lines)).enumerate() {
+        if index > 0 {
+            tokens.end_of_line();
+        }
+        add_line(tokens, line, kind);
+    }
+}
+
+/// Lex the code of `lines` with `lexer` in a fork of `tokens`, one list of
+/// nodes per line.
+fn lex_side(
+    lines: &[&str],
+    tokens: &HighlighterTokenizer,
+    lexer: &dyn lexer::Lexer,
+) -> Vec<Vec<hast::Node>> {
+    let mut side = tokens.fork();
+    lexer.lex(lines.join("\n").chars().collect(), &mut side);
+    side.end_of_line();
+    side.get_highlighted_code()
+        .into_iter()
+        .map(|row| match row {
+            hast::Node::Element(element) => element.children,
+            _ => vec![],
+        })
+        .collect()
+}
+
+/// Lex a diff of code in the language of `lexer` (`diff-ts`).
+///
+/// The `+`, `-` or space before each line of code becomes the marker of its
+/// row. The old code (context and removed lines) and the new code (context
+/// and added lines) of consecutive lines are lexed separately, so that a
+/// string opened on a removed line does not run into added lines.
+pub fn tokenize_language(
+    input: Vec<char>,
+    tokens: &mut HighlighterTokenizer,
+    lexer: &dyn lexer::Lexer,
+) {
+    let input: String = input.iter().collect();
+    let lines: Vec<&str> = input.split('\n').collect();
+    let kinds = line_kinds(&lines);
+    let mut index = 0;
+    while index < lines.len() {
+        if !kinds[index].is_code() {
+            if index > 0 {
+                tokens.end_of_line();
+            }
+            add_line(tokens, lines[index], kinds[index]);
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < lines.len() && kinds[index].is_code() {
+            index += 1;
+        }
+        let markers: Vec<&str> = lines[start..index]
+            .iter()
+            .zip(&kinds[start..index])
+            .map(|(line, kind)| match kind {
+                Line::Context if !line.starts_with(' ') => "",
+                _ => &line[..line.chars().next().map(char::len_utf8).unwrap_or(0)],
+            })
+            .collect();
+        let code = |kind: Line| -> Vec<&str> {
+            lines[start..index]
+                .iter()
+                .zip(&kinds[start..index])
+                .zip(&markers)
+                .filter(|((_, line_kind), _)| **line_kind == Line::Context || **line_kind == kind)
+                .map(|((line, _), marker)| &line[marker.len()..])
+                .collect()
+        };
+        let mut old = lex_side(&code(Line::Removed), tokens, lexer).into_iter();
+        let mut new = lex_side(&code(Line::Added), tokens, lexer).into_iter();
+
+        for (offset, kind) in kinds[start..index].iter().enumerate() {
+            if start + offset > 0 {
+                tokens.end_of_line();
+            }
+            let nodes = match kind {
+                Line::Removed => old.next(),
+                Line::Added => new.next(),
+                _ => {
+                    old.next();
+                    new.next()
+                }
+            };
+            let line = tokens.line();
+            tokens.line_properties(
+                line,
+                LineProperties {
+                    kind: kind.kind(),
+                    marker: Some(markers[offset])
+                        .filter(|marker| !marker.is_empty())
+                        .map(String::from),
+                },
+            );
+            tokens.add_nodes(nodes.unwrap_or_default());
+        }
+    }
+}
+
+pub struct DiffLexer;
+
+impl lexer::Lexer for DiffLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, row_class_names, to_code, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diff() {
+        let code = "diff --git a/a.md b/a.md\nindex 1a2b3c4..5d6e7f8 100644\n--- a/a.md\n+++ b/a.md\n@@ -1,3 +1,3 @@ # Title\n context\n---- removed\n+++ added\n\n\\ No newline at end of file";
+        let rows = highlight(code, "diff");
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec![
+                "diff --git a/a.md b/a.md",
+                "index 1a2b3c4..5d6e7f8 100644",
+                "--- a/a.md",
+                "+++ b/a.md"
+            ],
+            "should highlight file headers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["@@ -1,3 +1,3 @@"],
+            "should highlight hunk headers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ins"),
+            vec!["+++ added"],
+            "should highlight added lines, even if they look like headers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-del"),
+            vec!["---- removed"],
+            "should highlight removed lines, even if they look like headers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["\\ No newline at end of file"],
+            "should highlight notes"
+        );
+        assert_eq!(
+            row_class_names(&rows)[5..9],
+            ["hl-l", "hl-l hl-l-del", "hl-l hl-l-add", "hl-l"],
+            "should add the kind of lines to their rows"
+        );
+        assert_eq!(to_code(&rows), code, "should keep the code");
+    }
+
+    #[test]
+    fn language() {
+        let code = "--- a/a.ts\n+++ b/a.ts\n@@ -1,2 +1,2 @@\n const a = `\n-b`\n+c`\n\nlet d";
+        let rows = highlight(code, "diff-TS");
+        assert_eq!(
+            with_class(&rows, "hl-mk"),
+            vec![" ", "-", "+"],
+            "should turn the first column of code into markers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["`", "b`", "c`"],
+            "should lex code with the lexer of the language"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["const", "let"],
+            "should lex code outside of hunks"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["--- a/a.ts", "+++ b/a.ts"],
+            "should highlight headers as in diffs"
+        );
+        assert_eq!(
+            row_class_names(&rows)[3..6],
+            ["hl-l", "hl-l hl-l-del", "hl-l hl-l-add"],
+            "should add the kind of lines to their rows"
+        );
+        assert_eq!(to_code(&rows), code, "should keep the code");
+    }
+
+    #[test]
+    fn snippet() {
+        let rows = highlight("  a\n- b\n+ c\n-- d", "patch");
+        assert_eq!(
+            row_class_names(&rows),
+            vec!["hl-l", "hl-l hl-l-del", "hl-l hl-l-add", "hl-l hl-l-del"],
+            "should highlight diffs without headers"
+        );
+    }
+}
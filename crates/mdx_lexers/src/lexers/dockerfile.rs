@@ -0,0 +1,459 @@
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::{self, shell},
+    token::Token,
+};
+
+const INSTRUCTIONS: [&str; 18] = [
+    "ADD",
+    "ARG",
+    "CMD",
+    "COPY",
+    "ENTRYPOINT",
+    "ENV",
+    "EXPOSE",
+    "FROM",
+    "HEALTHCHECK",
+    "LABEL",
+    "MAINTAINER",
+    "ONBUILD",
+    "RUN",
+    "SHELL",
+    "STOPSIGNAL",
+    "USER",
+    "VOLUME",
+    "WORKDIR",
+];
+
+/// Lexer of the arguments of an instruction.
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+}
+
+fn is_name_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Input from the current position.
+    fn rest(&self) -> Vec<char> {
+        self.input[self.position.min(self.input.len())..].to_vec()
+    }
+
+    fn is_flag_start(&self) -> bool {
+        self.ch == '-' && self.peek_char(1) == '-' && is_name_start(self.peek_char(2))
+    }
+
+    fn is_word_char(&self) -> bool {
+        !(self.ch.is_whitespace()
+            || matches!(self.ch, '=' | '"' | '\'' | '$' | ',' | '[' | ']')
+            || (self.ch == '\\' && self.peek_char(1) == '\n'))
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            // Line continuations
+            '\\' if self.peek_char(1) == '\n' => {
+                self.read_char();
+                Token::CH('\\')
+            }
+            '-' if self.is_flag_start() => {
+                while self.position < self.input.len()
+                    && (self.ch.is_ascii_alphanumeric() || self.ch == '-')
+                {
+                    self.read_char();
+                }
+                Token::ATTRIBUTE(self.input[position..self.position].to_vec())
+            }
+            '"' | '\'' => {
+                let quote = self.ch;
+                self.read_char();
+                while self.position < self.input.len() && self.ch != quote && self.ch != '\n' {
+                    if quote == '"' && self.ch == '\\' && self.peek_char(1) != '\n' {
+                        self.read_char();
+                    }
+                    self.read_char();
+                }
+                if self.ch == quote {
+                    self.read_char();
+                }
+                Token::STRING(self.input[position..self.position.min(self.input.len())].to_vec())
+            }
+            '$' if self.peek_char(1) == '{' || is_name_start(self.peek_char(1)) => {
+                self.read_char();
+                if self.ch == '{' {
+                    while self.position < self.input.len() && self.ch != '}' && self.ch != '\n' {
+                        self.read_char();
+                    }
+                    if self.ch == '}' {
+                        self.read_char();
+                    }
+                } else {
+                    while self.position < self.input.len()
+                        && (self.ch.is_ascii_alphanumeric() || self.ch == '_')
+                    {
+                        self.read_char();
+                    }
+                }
+                Token::VAR(self.input[position..self.position.min(self.input.len())].to_vec())
+            }
+            ch if !self.is_word_char() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            _ => {
+                while self.position < self.input.len() && self.is_word_char() {
+                    self.read_char();
+                }
+                Token::IDENT(self.input[position..self.position].to_vec())
+            }
+        }
+    }
+
+    /// Add the whitespace from the current position.
+    fn add_whitespace(&mut self, tokens: &mut HighlighterTokenizer) {
+        while self.ch.is_whitespace() || (self.ch == '\\' && self.peek_char(1) == '\n') {
+            let token = self.next_token();
+            lexers::add_token(tokens, token);
+        }
+    }
+
+    /// Add flags (`--from=build`) from the current position.
+    fn add_flags(&mut self, tokens: &mut HighlighterTokenizer) {
+        self.add_whitespace(tokens);
+        while self.is_flag_start() {
+            let token = self.next_token();
+            lexers::add_token(tokens, token);
+            if self.ch == '=' {
+                let token = self.next_token();
+                lexers::add_token(tokens, token);
+                // Values of flags (`type=cache,target=/a`)
+                while self.position < self.input.len() && !self.ch.is_whitespace() {
+                    let token = self.next_token();
+                    lexers::add_token(tokens, token);
+                }
+            }
+            self.add_whitespace(tokens);
+        }
+    }
+}
+
+/// Lex the command of `RUN`, `CMD`, `ENTRYPOINT` or `SHELL`: the exec form
+/// (`["a", "b"]`) as a list of strings, the shell form with the shell lexer.
+fn tokenize_command(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    l.add_flags(tokens);
+    if l.ch == '[' {
+        tokenize_arguments(l, "", tokens);
+    } else {
+        shell::tokenize(l.rest(), tokens);
+    }
+}
+
+/// Lex the arguments of the instruction `name`.
+fn tokenize_arguments(l: &mut Lexer, name: &str, tokens: &mut HighlighterTokenizer) {
+    // Pairs without `=` (`ENV A b`)
+    let legacy_pair = !l.rest().contains(&'=');
+    let mut first = true;
+    let mut after_equals = false;
+    loop {
+        let token = l.next_token();
+        let token = match token {
+            Token::EOF => break,
+            Token::CH(ch) if ch.is_whitespace() || ch == '\\' => token,
+            Token::IDENT(value) => {
+                let word: String = value.iter().collect();
+                let is_key = l.ch == '=' || (first && legacy_pair);
+                let is_value = after_equals;
+                first = false;
+                after_equals = false;
+                match name {
+                    "FROM" if word.eq_ignore_ascii_case("as") => Token::KEYWORD(value),
+                    // Images and names of stages
+                    "FROM" if !is_value => Token::ENTITY(value),
+                    "ARG" | "ENV" if is_key => Token::VAR(value),
+                    "LABEL" if is_key => Token::ATTRIBUTE(value),
+                    _ => Token::IDENT(value),
+                }
+            }
+            token => {
+                after_equals = token == Token::CH('=');
+                token
+            }
+        };
+        lexers::add_token(tokens, token);
+    }
+}
+
+/// Lex an instruction, which may span lines.
+fn tokenize_instruction(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    l.add_whitespace(tokens);
+    let token = l.next_token();
+    let name = match &token {
+        Token::IDENT(value) => value.iter().collect::<String>().to_uppercase(),
+        _ => String::new(),
+    };
+    if !INSTRUCTIONS.contains(&&name[..]) {
+        lexers::add_token(tokens, token);
+        tokenize_arguments(&mut l, "", tokens);
+        return;
+    }
+    if let Token::IDENT(value) = token {
+        lexers::add_token(tokens, Token::KEYWORD(value));
+    }
+
+    match &name[..] {
+        "RUN" | "CMD" | "ENTRYPOINT" | "SHELL" => tokenize_command(&mut l, tokens),
+        "HEALTHCHECK" => {
+            l.add_flags(tokens);
+            let token = l.next_token();
+            match token {
+                Token::IDENT(value)
+                    if value.iter().collect::<String>().eq_ignore_ascii_case("cmd") =>
+                {
+                    lexers::add_token(tokens, Token::KEYWORD(value));
+                    tokenize_command(&mut l, tokens);
+                }
+                Token::IDENT(value) => lexers::add_token(tokens, Token::KEYWORD(value)),
+                token => lexers::add_token(tokens, token),
+            }
+        }
+        // Instructions of images built from this one (`ONBUILD RUN a`)
+        "ONBUILD" => {
+            l.add_whitespace(tokens);
+            tokenize_instruction(l.rest(), tokens);
+        }
+        name => {
+            l.add_flags(tokens);
+            tokenize_arguments(&mut l, name, tokens);
+        }
+    }
+}
+
+/// Delimiters of the heredocs in `line` (`RUN <<EOF`, `COPY <<-"A" /a`).
+///
+/// Shifts in arithmetic (`$((1 << 2))`) and here-strings (`<<<a`) are not
+/// heredocs.
+fn heredoc_delimiters(line: &[char]) -> Vec<String> {
+    let mut delimiters = vec![];
+    // Parentheses open in arithmetic expansions
+    let mut arithmetic = 0;
+    let mut index = 0;
+    while index + 2 < line.len() {
+        if line[index..].starts_with(&['$', '(', '(']) {
+            arithmetic += 2;
+            index += 3;
+        } else if arithmetic > 0 {
+            match line[index] {
+                '(' => arithmetic += 1,
+                ')' => arithmetic -= 1,
+                _ => {}
+            }
+            index += 1;
+        } else if line[index..].starts_with(&['<', '<', '<']) {
+            index += 3;
+        } else if line[index] == '<' && line[index + 1] == '<' {
+            index += 2;
+            if line.get(index) == Some(&'-') {
+                index += 1;
+            }
+            let quote = match line.get(index) {
+                Some(ch @ ('"' | '\'')) => {
+                    index += 1;
+                    Some(*ch)
+                }
+                _ => None,
+            };
+            let start = index;
+            while index < line.len() && (line[index].is_ascii_alphanumeric() || line[index] == '_')
+            {
+                index += 1;
+            }
+            if index > start && !line[start].is_ascii_digit() {
+                delimiters.push(line[start..index].iter().collect());
+            }
+            if quote.is_some() && line.get(index) == quote.as_ref() {
+                index += 1;
+            }
+        } else {
+            index += 1;
+        }
+    }
+    delimiters
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let lines: Vec<&[char]> = input.split(|ch| *ch == '\n').collect();
+    let mut index = 0;
+    while index < lines.len() {
+        if index > 0 {
+            tokens.end_of_line();
+        }
+        let line = lines[index];
+        let indent = line.iter().take_while(|ch| ch.is_whitespace()).count();
+        if indent == line.len() {
+            tokens.add_text(line.iter().collect());
+        } else if line[indent] == '#' {
+            tokens.add_text(line[..indent].iter().collect());
+            lexers::add_token(tokens, Token::COMMENT(line[indent..].to_vec()));
+        } else {
+            // Instructions continued with `\` on the next lines
+            let mut instruction = line.to_vec();
+            while instruction
+                .iter()
+                .rev()
+                .find(|ch| !matches!(ch, ' ' | '\t'))
+                == Some(&'\\')
+                && index + 1 < lines.len()
+            {
+                index += 1;
+                instruction.push('\n');
+                instruction.extend_from_slice(lines[index]);
+            }
+            // Bodies of heredocs, up to their delimiter
+            for delimiter in heredoc_delimiters(&instruction) {
+                while index + 1 < lines.len() {
+                    index += 1;
+                    instruction.push('\n');
+                    instruction.extend_from_slice(lines[index]);
+                    if lines[index].iter().collect::<String>().trim() == delimiter {
+                        break;
+                    }
+                }
+            }
+            tokenize_instruction(instruction, tokens);
+        }
+        index += 1;
+    }
+}
+
+pub struct DockerfileLexer;
+
+impl lexer::Lexer for DockerfileLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dockerfile() {
+        let rows = highlight(
+            "# syntax=docker/dockerfile:1\nFROM --platform=$BUILDPLATFORM node:20 AS build\nARG VERSION=1.0\nENV NODE_ENV=\"production\" PORT=3000\nLABEL org.opencontainers.image.title=\"app\"\nRUN --mount=type=cache,target=/root/.npm \\\n    npm ci && echo \"$VERSION\"\nCOPY --from=build /app/dist ./dist\nCMD [\"node\", \"dist/index.js\"]",
+            "dockerfile",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["FROM", "AS", "ARG", "ENV", "LABEL", "RUN", "COPY", "CMD"],
+            "should highlight instructions"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec![
+                "--platform",
+                "org.opencontainers.image.title",
+                "--mount",
+                "--from"
+            ],
+            "should highlight flags and labels"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["$BUILDPLATFORM", "VERSION", "NODE_ENV", "PORT", "$VERSION"],
+            "should highlight variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["node:20", "build", "echo"],
+            "should highlight images, stages and built-in commands"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec![
+                "\"production\"",
+                "\"app\"",
+                "\"",
+                "\"",
+                "\"node\"",
+                "\"dist/index.js\""
+            ],
+            "should highlight strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# syntax=docker/dockerfile:1"],
+            "should highlight comments"
+        );
+    }
+
+    #[test]
+    fn heredoc() {
+        let rows = highlight("RUN <<EOF\nFROM a\nEOF\nUSER b", "dockerfile");
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["RUN", "USER"],
+            "should not lex heredocs as instructions"
+        );
+
+        let rows = highlight(
+            "RUN echo $((1<<2)) && cat <<<a\nRUN echo $(( (1) <<b ))\nUSER c",
+            "dockerfile",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["RUN", "RUN", "USER"],
+            "should not see shifts and here-strings as heredocs"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Lexer for Go.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ],
+    types: &[
+        "any",
+        "bool",
+        "byte",
+        "comparable",
+        "complex64",
+        "complex128",
+        "error",
+        "float32",
+        "float64",
+        "int",
+        "int8",
+        "int16",
+        "int32",
+        "int64",
+        "rune",
+        "string",
+        "uint",
+        "uint8",
+        "uint16",
+        "uint32",
+        "uint64",
+        "uintptr",
+    ],
+    constants: &["true", "false", "nil", "iota"],
+    capitalized_types: false,
+    nested_comments: false,
+    strings: &[
+        StringSyntax::Double,
+        StringSyntax::Char,
+        StringSyntax::Backtick,
+    ],
+    string_prefixes: &[],
+    annotations: AnnotationSyntax::None,
+    preprocessor: false,
+};
+
+pub struct GoLexer;
+
+impl lexer::Lexer for GoLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn go() {
+        let rows = highlight(
+            "package main\n\nfunc main() {\n\tvar r rune = 'a'\n\ts := `raw\n\\n`\n\tfmt.Println(s, r, nil)\n}",
+            "go",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["package", "func", "var"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["rune"],
+            "should highlight built-in types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["'a'", "`raw", "\\n`"],
+            "should highlight runes and raw strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["main", "Println"],
+            "should highlight functions without treating exported names as types"
+        );
+    }
+}
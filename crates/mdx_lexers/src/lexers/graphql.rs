@@ -0,0 +1,427 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+/// Definitions of the type system, whose bodies define fields and types.
+const TYPE_SYSTEM_KEYWORDS: [&str; 8] = [
+    "type",
+    "interface",
+    "union",
+    "enum",
+    "input",
+    "scalar",
+    "schema",
+    "directive",
+];
+
+/// Definitions of executable documents, whose bodies select fields.
+const EXECUTABLE_KEYWORDS: [&str; 4] = ["query", "mutation", "subscription", "fragment"];
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Open selection sets and bodies of definitions (`{`).
+    braces: usize,
+    /// Open arguments and variable definitions (`(`); braces in them are
+    /// input objects.
+    parens: usize,
+    /// Keyword of the current top-level definition, empty for the shorthand
+    /// query (`{ a }`).
+    definition: String,
+    /// Last token that is not whitespace or a comment.
+    last: String,
+    /// Whether the next name is a type (`a: [Int!]`).
+    expect_type: bool,
+    /// Whether the next name is a value (`a: ACTIVE`).
+    expect_value: bool,
+}
+
+fn is_name_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            braces: 0,
+            parens: 0,
+            definition: String::new(),
+            last: String::new(),
+            expect_type: false,
+            expect_value: false,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Next character from the current position that is not whitespace.
+    fn next_char(&self) -> char {
+        self.input[self.position.min(self.input.len())..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    fn is_type_system(&self) -> bool {
+        TYPE_SYSTEM_KEYWORDS.contains(&&self.definition[..])
+    }
+
+    fn read_name(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_name_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Read a string; block strings (`"""a"""`) may span several lines.
+    fn read_string(&mut self) -> Vec<char> {
+        let position = self.position;
+        let block = self.peek_char(1) == '"' && self.peek_char(2) == '"';
+        if block {
+            self.read_char();
+            self.read_char();
+        }
+        self.read_char();
+        while self.position < self.input.len() && (block || self.ch != '\n') {
+            if block && self.ch == '\\' && self.peek_char(1) == '"' {
+                // Escaped block quote (`\"""`)
+                self.read_char();
+                self.read_char();
+                self.read_char();
+            } else if !block && self.ch == '\\' {
+                self.read_char();
+            } else if self.ch == '"'
+                && (!block || (self.peek_char(1) == '"' && self.peek_char(2) == '"'))
+            {
+                break;
+            }
+            self.read_char();
+        }
+        if block {
+            self.read_char();
+            self.read_char();
+        }
+        if self.ch == '"' {
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        if self.ch == '-' {
+            self.read_char();
+        }
+        while self.position < self.input.len() {
+            if matches!(self.ch, 'e' | 'E') && matches!(self.peek_char(1), '+' | '-') {
+                self.read_char();
+            } else if !(self.ch.is_ascii_alphanumeric() || self.ch == '.') {
+                break;
+            }
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Token of a name, from where it is.
+    fn name_token(&mut self, name: Vec<char>, expect_type: bool, expect_value: bool) -> Token {
+        let value: String = name.iter().collect();
+        let top_level = self.braces == 0 && self.parens == 0;
+        let last = &self.last[..];
+
+        if last == "..." {
+            // Inline fragments (`... on A`) and fragment spreads (`...a`)
+            if value == "on" {
+                Token::KEYWORD(name)
+            } else {
+                Token::ENTITY(name)
+            }
+        } else if self.definition == "directive" && top_level && matches!(last, "on" | "|") {
+            // Locations of directives (`on FIELD | QUERY`)
+            Token::CONSTANT(name)
+        } else if expect_type || last == "on" {
+            Token::TYPE(name)
+        } else if top_level
+            && (value == "on"
+                || value == "extend"
+                || value == "implements"
+                || value == "repeatable"
+                || TYPE_SYSTEM_KEYWORDS.contains(&&value[..])
+                || EXECUTABLE_KEYWORDS.contains(&&value[..]))
+            && !TYPE_SYSTEM_KEYWORDS.contains(&last)
+            && !EXECUTABLE_KEYWORDS.contains(&last)
+        {
+            if value != "on" && value != "implements" && value != "repeatable" {
+                self.definition = value;
+            }
+            Token::KEYWORD(name)
+        } else if top_level
+            && (matches!(last, "implements" | "&" | "|" | "=")
+                || TYPE_SYSTEM_KEYWORDS.contains(&last))
+        {
+            Token::TYPE(name)
+        } else if top_level && EXECUTABLE_KEYWORDS.contains(&last) {
+            // Names of operations and fragments
+            Token::ENTITY(name)
+        } else if matches!(&value[..], "true" | "false" | "null") || expect_value {
+            Token::CONSTANT(name)
+        } else if self.parens > 0 && self.next_char() == ':' {
+            Token::ATTRIBUTE(name)
+        } else if self.braces > 0 && self.definition == "enum" {
+            Token::CONSTANT(name)
+        } else if self.braces > 0 {
+            Token::ENTITY(name)
+        } else {
+            Token::IDENT(name)
+        }
+    }
+
+    /// Update the state after the punctuation `ch`.
+    fn punctuation(&mut self, ch: char, expect_type: bool) {
+        match ch {
+            ':' => {
+                // Types of variables (`query ($a: Int)`), arguments and
+                // fields of the type system, values of arguments otherwise
+                if self.is_type_system() || (self.parens > 0 && self.braces == 0) {
+                    self.expect_type = true;
+                } else if self.parens > 0 {
+                    self.expect_value = true;
+                }
+            }
+            '=' if self.parens > 0 => self.expect_value = true,
+            '[' => self.expect_type = expect_type,
+            '(' => self.parens += 1,
+            ')' => self.parens = self.parens.saturating_sub(1),
+            '{' if self.parens == 0 => {
+                // Shorthand queries (`{ a }`)
+                if self.braces == 0 && matches!(&self.last[..], "" | "}") {
+                    self.definition = String::new();
+                }
+                self.braces += 1;
+            }
+            '}' if self.parens == 0 => self.braces = self.braces.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        let token = match self.ch {
+            '\n' => {
+                self.read_char();
+                return Token::ENDL('\n');
+            }
+            ch if ch.is_whitespace() || ch == ',' => {
+                self.read_char();
+                return Token::CH(ch);
+            }
+            '#' => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                return Token::COMMENT(self.input[position..self.position].to_vec());
+            }
+            _ => {
+                let expect_type = self.expect_type;
+                let expect_value = self.expect_value;
+                self.expect_type = false;
+                self.expect_value = false;
+                self.significant_token(expect_type, expect_value)
+            }
+        };
+        self.last = self.input[position..self.position.min(self.input.len())]
+            .iter()
+            .collect();
+        token
+    }
+
+    /// Token that is not whitespace or a comment.
+    fn significant_token(&mut self, expect_type: bool, expect_value: bool) -> Token {
+        let position = self.position;
+        match self.ch {
+            '"' => Token::STRING(self.read_string()),
+            '.' if self.peek_char(1) == '.' && self.peek_char(2) == '.' => {
+                self.read_char();
+                self.read_char();
+                self.read_char();
+                Token::PUNCTUATION(self.input[position..self.position].to_vec())
+            }
+            '$' if is_name_start(self.peek_char(1)) => {
+                self.read_char();
+                self.read_name();
+                Token::VAR(self.input[position..self.position].to_vec())
+            }
+            '@' if is_name_start(self.peek_char(1)) => {
+                self.read_char();
+                self.read_name();
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            ch if ch.is_ascii_digit() || (ch == '-' && self.peek_char(1).is_ascii_digit()) => {
+                Token::INT(self.read_number())
+            }
+            ch if is_name_start(ch) => {
+                let name = self.read_name();
+                self.name_token(name, expect_type, expect_value)
+            }
+            ch => {
+                self.read_char();
+                self.punctuation(ch, expect_type);
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct GraphqlLexer;
+
+impl lexer::Lexer for GraphqlLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn query() {
+        let rows = highlight(
+            "# Users\nquery GetUser($id: ID!, $all: [String!] = [\"a\"]) {\n  user(id: $id, status: ACTIVE) {\n    name: fullName @include(if: $all)\n    ...UserFields\n    ... on Admin { type }\n  }\n}\n\nfragment UserFields on User { id }",
+            "gql",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["query", "on", "fragment", "on"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec![
+                "GetUser",
+                "user",
+                "name",
+                "fullName",
+                "UserFields",
+                "type",
+                "UserFields",
+                "id"
+            ],
+            "should highlight operations, fields and fragments as entities"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["ID", "String", "Admin", "User"],
+            "should highlight types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["$id", "$all", "$id", "$all"],
+            "should highlight variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["id", "status", "if"],
+            "should highlight arguments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["ACTIVE"],
+            "should highlight enum values"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["@include"],
+            "should highlight directives"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["...", "..."],
+            "should highlight spreads"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# Users"],
+            "should highlight comments"
+        );
+    }
+
+    #[test]
+    fn schema() {
+        let rows = highlight(
+            "\"\"\"\nA user, \\\"\"\" quoted\n\"\"\"\ntype User implements Node & Entity {\n  id: ID!\n  posts(first: Int = 10): [Post!]! @deprecated(reason: \"No\")\n}\n\nenum Role { ADMIN USER }\nunion Result = User | Error\ndirective @auth on FIELD_DEFINITION | OBJECT",
+            "graphql",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"\"\"", "A user, \\\"\"\" quoted", "\"\"\"", "\"No\""],
+            "should highlight block strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["type", "implements", "enum", "union", "directive", "on"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["User", "Node", "Entity", "ID", "Int", "Post", "Role", "Result", "User", "Error"],
+            "should highlight types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["id", "posts"],
+            "should highlight fields"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["10", "ADMIN", "USER", "FIELD_DEFINITION", "OBJECT"],
+            "should highlight enum values and directive locations"
+        );
+    }
+}
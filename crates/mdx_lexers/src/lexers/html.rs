@@ -0,0 +1,434 @@
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer::{self, Lexer as _},
+    lexers::{self, css::CssLexer, javascript::JavascriptLexer, raw::RawLexer},
+    token::Token,
+};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Whether the lexer is between the `<` and `>` of a tag.
+    in_tag: bool,
+    /// Whether the lexer is in a CDATA section.
+    in_cdata: bool,
+    /// Whether the next name in the tag is its name.
+    expect_tag_name: bool,
+    /// Whether the next name in the tag is the value of an attribute.
+    expect_value: bool,
+    /// Whether the current tag is a closing tag (`</a>`).
+    closing_tag: bool,
+    /// Whether the current tag is self-closing (`<a/>`).
+    self_closing_tag: bool,
+    tag_name: String,
+    attribute_name: String,
+    /// Value of the `type` attribute of the current tag.
+    type_attribute: Option<String>,
+    /// Element whose content follows and must be lexed by another lexer
+    /// (`script`, `style`), with its `type` attribute.
+    embedded: Option<(String, Option<String>)>,
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '-' | '_' | ':' | '.')
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            in_tag: false,
+            in_cdata: false,
+            expect_tag_name: false,
+            expect_value: false,
+            closing_tag: false,
+            self_closing_tag: false,
+            tag_name: String::new(),
+            attribute_name: String::new(),
+            type_attribute: None,
+            embedded: None,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Whether `value` is at the current position.
+    fn starts_with(&self, value: &str) -> bool {
+        value
+            .chars()
+            .enumerate()
+            .all(|(offset, ch)| self.peek_char(offset) == ch)
+    }
+
+    /// Read up to and including `end`, or up to the end of the input.
+    fn read_until(&mut self, end: &str) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && !self.starts_with(end) {
+            self.read_char();
+        }
+        for _ in 0..end.chars().count() {
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    fn read_name(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_name_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Read a character reference (`&amp;`, `&#123;`, `&#x1F;`), if there is
+    /// one at the current position.
+    fn read_entity(&mut self) -> Option<Vec<char>> {
+        let mut offset = 1;
+        while self.peek_char(offset).is_alphanumeric() || (offset == 1 && self.peek_char(1) == '#')
+        {
+            offset += 1;
+        }
+        if offset == 1 || self.peek_char(offset) != ';' {
+            return None;
+        }
+        let position = self.position;
+        for _ in 0..=offset {
+            self.read_char();
+        }
+        Some(self.input[position..self.position].to_vec())
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+        if self.ch == '\n' {
+            self.read_char();
+            return Token::ENDL('\n');
+        }
+        if self.in_cdata {
+            if self.starts_with("]]>") {
+                self.in_cdata = false;
+                return Token::META(self.read_until("]]>"));
+            }
+            while self.position < self.input.len() && self.ch != '\n' && !self.starts_with("]]>") {
+                self.read_char();
+            }
+            return Token::IDENT(self.input[position..self.position].to_vec());
+        }
+        if self.in_tag {
+            return self.next_tag_token(position);
+        }
+
+        if self.starts_with("<!--") {
+            return Token::COMMENT(self.read_until("-->"));
+        }
+        if self.starts_with("<![CDATA[") {
+            self.in_cdata = true;
+            return Token::META(self.read_until("<![CDATA["));
+        }
+        if self.starts_with("<!") {
+            return Token::META(self.read_until(">"));
+        }
+        if self.starts_with("<?") {
+            return Token::META(self.read_until("?>"));
+        }
+        if self.ch == '<'
+            && (self.peek_char(1).is_alphabetic()
+                || (self.peek_char(1) == '/' && self.peek_char(2).is_alphabetic()))
+        {
+            self.in_tag = true;
+            self.expect_tag_name = true;
+            self.expect_value = false;
+            self.closing_tag = false;
+            self.self_closing_tag = false;
+            self.type_attribute = None;
+            self.read_char();
+            return Token::CH('<');
+        }
+        if self.ch == '&' {
+            if let Some(entity) = self.read_entity() {
+                return Token::CONSTANT(entity);
+            }
+        }
+
+        // Text
+        self.read_char();
+        while self.position < self.input.len() && !matches!(self.ch, '<' | '&' | '\n') {
+            self.read_char();
+        }
+        Token::IDENT(self.input[position..self.position].to_vec())
+    }
+
+    fn next_tag_token(&mut self, position: usize) -> Token {
+        match self.ch {
+            '>' => {
+                self.in_tag = false;
+                let tag_name = self.tag_name.to_lowercase();
+                if !self.closing_tag
+                    && !self.self_closing_tag
+                    && matches!(&tag_name[..], "script" | "style")
+                {
+                    self.embedded = Some((tag_name, self.type_attribute.take()));
+                }
+                self.read_char();
+                Token::CH('>')
+            }
+            '/' => {
+                if self.expect_tag_name {
+                    self.closing_tag = true;
+                } else {
+                    self.self_closing_tag = true;
+                }
+                self.read_char();
+                Token::CH('/')
+            }
+            '=' => {
+                self.expect_value = true;
+                self.read_char();
+                Token::CH('=')
+            }
+            '"' | '\'' => {
+                let quote = self.ch;
+                self.read_char();
+                while self.position < self.input.len() && self.ch != quote {
+                    self.read_char();
+                }
+                self.read_char();
+                let value = self.input[position..self.position.min(self.input.len())].to_vec();
+                let unquoted: Vec<char> = value[1..]
+                    .iter()
+                    .take_while(|ch| **ch != quote)
+                    .copied()
+                    .collect();
+                self.set_value(&unquoted);
+                Token::STRING(value)
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            _ if self.expect_value => {
+                while self.position < self.input.len()
+                    && !self.ch.is_whitespace()
+                    && !matches!(self.ch, '>' | '"' | '\'')
+                {
+                    self.read_char();
+                }
+                let value = self.input[position..self.position].to_vec();
+                self.set_value(&value);
+                Token::STRING(value)
+            }
+            ch if is_name_char(ch) => {
+                let name = self.read_name();
+                if self.expect_tag_name {
+                    self.expect_tag_name = false;
+                    self.tag_name = name.iter().collect();
+                    Token::ENTITYTAG(name)
+                } else {
+                    self.attribute_name = name.iter().collect::<String>().to_lowercase();
+                    Token::ATTRIBUTE(name)
+                }
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+
+    /// Keep the value of the attribute that was just read.
+    fn set_value(&mut self, value: &[char]) {
+        self.expect_value = false;
+        if self.attribute_name == "type" {
+            self.type_attribute = Some(value.iter().collect::<String>().to_lowercase());
+        }
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    loop {
+        if let Some((tag_name, type_attribute)) = l.embedded.take() {
+            read_embedded(&mut l, &tag_name, type_attribute, tokens);
+        }
+
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+/// Read the content of a `script` or `style` element and lex it with the
+/// lexer of its language, into the same rows.
+fn read_embedded(
+    l: &mut Lexer,
+    tag_name: &str,
+    type_attribute: Option<String>,
+    tokens: &mut HighlighterTokenizer,
+) {
+    let start_position = l.position.min(l.input.len());
+    let closing_tag: Vec<char> = format!("</{}", tag_name).chars().collect();
+    let end_position = (start_position..l.input.len())
+        .find(|index| {
+            l.input[*index..]
+                .iter()
+                .take(closing_tag.len())
+                .map(|ch| ch.to_ascii_lowercase())
+                .eq(closing_tag.iter().copied())
+        })
+        .unwrap_or(l.input.len());
+    let content = l.input[start_position..end_position].to_vec();
+
+    let type_attribute = type_attribute.unwrap_or_default();
+    if tag_name == "style" {
+        CssLexer {
+            line_comments: false,
+        }
+        .lex(content, tokens);
+    } else if type_attribute.is_empty()
+        || type_attribute == "module"
+        || type_attribute.contains("javascript")
+        || type_attribute.contains("ecmascript")
+    {
+        JavascriptLexer.lex(content, tokens);
+    } else {
+        // Templates, data
+        RawLexer.lex(content, tokens);
+    }
+
+    l.read_position = end_position;
+    l.read_char();
+}
+
+pub struct HtmlLexer;
+
+impl lexer::Lexer for HtmlLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn html() {
+        let rows = highlight(
+            "<!DOCTYPE html>\n<!-- Page\n-->\n<p class=\"a\" hidden id=b>Tom &amp; Jerry</p><br/>",
+            "html",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["<!DOCTYPE html>"],
+            "should highlight doctypes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["<!-- Page", "-->"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["p", "p", "br"],
+            "should highlight tag names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["class", "hidden", "id"],
+            "should highlight attribute names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"a\"", "b"],
+            "should highlight attribute values"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["&amp;"],
+            "should highlight entities"
+        );
+    }
+
+    #[test]
+    fn xml() {
+        let rows = highlight(
+            "<?xml version=\"1.0\"?>\n<svg:rect x=\"1\"><![CDATA[a < b\n]]></svg:rect>",
+            "xml",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["<?xml version=\"1.0\"?>", "<![CDATA[", "]]>"],
+            "should highlight processing instructions and CDATA"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["svg:rect", "svg:rect"],
+            "should highlight namespaced tag names"
+        );
+    }
+
+    #[test]
+    fn embedded() {
+        let rows = highlight(
+            "<style>\n  .a { color: red; }\n</style>\n<script>\n  const a = 1 < 2;\n</script>\n<SCRIPT type=\"text/template\"><p>{{a}}</p></SCRIPT>",
+            "html",
+        );
+        assert_eq!(rows.len(), 7, "should keep rows");
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["style", "style", "script", "script", "SCRIPT", "SCRIPT"],
+            "should highlight tag names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec![".a"],
+            "should lex styles as CSS"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["color", "type"],
+            "should lex styles"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["const"],
+            "should lex scripts as JavaScript"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1", "2"],
+            "should not lex other scripts"
+        );
+    }
+}
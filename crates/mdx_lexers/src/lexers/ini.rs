@@ -0,0 +1,366 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Whether to lex dotenv files (`export A="$B"`) instead of INI files.
+    dotenv: bool,
+    /// Whether the lexer is in a section header (`[a]`).
+    in_header: bool,
+    /// Whether the lexer is after the separator of a key (`=`).
+    in_value: bool,
+    /// Whether the lexer is in a double quoted value of a dotenv file.
+    in_string: bool,
+}
+
+fn is_name_start(ch: char) -> bool {
+    ch.is_ascii_alphabetic() || ch == '_'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>, dotenv: bool) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            dotenv,
+            in_header: false,
+            in_value: false,
+            in_string: false,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    fn previous_char(&self, position: usize) -> char {
+        if position == 0 {
+            '\n'
+        } else {
+            self.input[position - 1]
+        }
+    }
+
+    fn is_comment_start(&self, position: usize) -> bool {
+        let comment = if self.dotenv {
+            self.ch == '#'
+        } else {
+            self.ch == '#' || self.ch == ';'
+        };
+        // Comments after values need a space before them (`a = b # c`)
+        comment && (!self.in_value || self.previous_char(position).is_whitespace())
+    }
+
+    fn is_separator(&self) -> bool {
+        self.ch == '=' || (!self.dotenv && self.ch == ':')
+    }
+
+    /// Whether the lexer is at a reference to a variable (`$A`, `${A}`).
+    fn is_variable_start(&self) -> bool {
+        self.dotenv
+            && self.ch == '$'
+            && (self.peek_char(1) == '{' || is_name_start(self.peek_char(1)))
+    }
+
+    fn read_variable(&mut self) -> Vec<char> {
+        let position = self.position;
+        self.read_char();
+        if self.ch == '{' {
+            while self.position < self.input.len() && self.ch != '}' && self.ch != '\n' {
+                self.read_char();
+            }
+            if self.ch == '}' {
+                self.read_char();
+            }
+        } else {
+            while self.position < self.input.len()
+                && (self.ch.is_ascii_alphanumeric() || self.ch == '_')
+            {
+                self.read_char();
+            }
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Read an unquoted value up to the end of the line, a comment, or a
+    /// variable, without trailing whitespace.
+    fn read_value(&mut self) -> Vec<char> {
+        let position = self.position;
+        let mut end = position;
+        while self.position < self.input.len()
+            && self.ch != '\n'
+            && !self.is_comment_start(self.position)
+            && !self.is_variable_start()
+        {
+            if !self.ch.is_whitespace() {
+                end = self.position + 1;
+            }
+            self.read_char();
+        }
+        // Leave trailing whitespace to be lexed as text
+        self.position = end;
+        self.read_position = end;
+        self.read_char();
+        self.input[position..end].to_vec()
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        if self.in_string {
+            return match self.ch {
+                '"' => {
+                    self.in_string = false;
+                    self.read_char();
+                    Token::STRING(vec!['"'])
+                }
+                _ if self.is_variable_start() => Token::VAR(self.read_variable()),
+                _ => {
+                    while self.position < self.input.len()
+                        && self.ch != '"'
+                        && !self.is_variable_start()
+                    {
+                        if self.ch == '\\' {
+                            self.read_char();
+                        }
+                        self.read_char();
+                    }
+                    Token::STRING(
+                        self.input[position..self.position.min(self.input.len())].to_vec(),
+                    )
+                }
+            };
+        }
+
+        match self.ch {
+            '\n' => {
+                self.in_header = false;
+                self.in_value = false;
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            _ if self.is_comment_start(position) => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::COMMENT(self.input[position..self.position].to_vec())
+            }
+            '[' if !self.dotenv && !self.in_value && !self.in_header => {
+                self.in_header = true;
+                self.read_char();
+                Token::PUNCTUATION(vec!['['])
+            }
+            ']' if self.in_header => {
+                self.in_header = false;
+                self.read_char();
+                Token::PUNCTUATION(vec![']'])
+            }
+            _ if self.in_header => {
+                while self.position < self.input.len() && self.ch != ']' && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::ENTITY(self.input[position..self.position].to_vec())
+            }
+            _ if !self.in_value && self.is_separator() => {
+                self.in_value = true;
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            _ if !self.in_value => {
+                while self.position < self.input.len()
+                    && self.ch != '\n'
+                    && !self.is_separator()
+                    && !(self.dotenv && self.ch.is_whitespace())
+                {
+                    self.read_char();
+                }
+                let mut end = self.position;
+                while end > position && self.input[end - 1].is_whitespace() {
+                    end -= 1;
+                }
+                let key = self.input[position..end].to_vec();
+                if self.dotenv && key == ['e', 'x', 'p', 'o', 'r', 't'] && !self.is_separator() {
+                    Token::KEYWORD(key)
+                } else {
+                    self.position = end;
+                    self.read_position = end;
+                    self.read_char();
+                    Token::ATTRIBUTE(key)
+                }
+            }
+            '"' if self.dotenv => {
+                self.in_string = true;
+                self.read_char();
+                Token::STRING(vec!['"'])
+            }
+            '"' | '\'' => {
+                let quote = self.ch;
+                self.read_char();
+                while self.position < self.input.len()
+                    && self.ch != quote
+                    && (self.dotenv || self.ch != '\n')
+                {
+                    self.read_char();
+                }
+                if self.ch == quote {
+                    self.read_char();
+                }
+                Token::STRING(self.input[position..self.position.min(self.input.len())].to_vec())
+            }
+            _ if self.is_variable_start() => Token::VAR(self.read_variable()),
+            _ => {
+                let value = self.read_value();
+                let name: String = value.iter().collect::<String>().to_lowercase();
+                let unsigned = name.strip_prefix(['-', '+']).unwrap_or(&name);
+                match unsigned {
+                    "true" | "false" | "yes" | "no" | "on" | "off" => Token::CONSTANT(value),
+                    _ if !unsigned.is_empty()
+                        && unsigned.chars().all(|ch| ch.is_ascii_digit() || ch == '.') =>
+                    {
+                        Token::INT(value)
+                    }
+                    _ => Token::STRING(value),
+                }
+            }
+        }
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer, dotenv: bool) {
+    let mut l = Lexer::new(input, dotenv);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct IniLexer {
+    /// Whether to lex dotenv files (`.env`) instead of INI files.
+    pub dotenv: bool,
+}
+
+impl lexer::Lexer for IniLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.dotenv);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ini() {
+        let rows = highlight(
+            "; Settings\n[server]\nhost = example.com ; local\nport: 8080\ndebug=true\n# Paths\n[paths.data]\nroot = \"/var/lib\"",
+            "ini",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["; Settings", "; local", "# Paths"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["server", "paths.data"],
+            "should highlight sections"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["host", "port", "debug", "root"],
+            "should highlight keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["example.com", "\"/var/lib\""],
+            "should highlight values"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["8080", "true"],
+            "should highlight numbers and booleans"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["[", "]", "=", ":", "=", "[", "]", "="],
+            "should highlight punctuation"
+        );
+    }
+
+    #[test]
+    fn dotenv() {
+        let rows = highlight(
+            "# Database\nexport DB_HOST=localhost\nDB_URL=\"postgres://${DB_HOST}:$PORT\" # url\nKEY='a#b'\nCERT=\"line\nline\"",
+            "dotenv",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["export"],
+            "should highlight `export`"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["DB_HOST", "DB_URL", "KEY", "CERT"],
+            "should highlight keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["${DB_HOST}", "$PORT"],
+            "should highlight variables in double quoted values"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# Database", "# url"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec![
+                "localhost",
+                "\"",
+                "postgres://",
+                ":",
+                "\"",
+                "'a#b'",
+                "\"",
+                "line",
+                "line",
+                "\""
+            ],
+            "should highlight values, across lines in double quotes"
+        );
+    }
+}
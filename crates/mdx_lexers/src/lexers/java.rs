@@ -0,0 +1,113 @@
+//! Lexer for Java.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "abstract",
+        "assert",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "default",
+        "do",
+        "else",
+        "enum",
+        "extends",
+        "final",
+        "finally",
+        "for",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "interface",
+        "native",
+        "new",
+        "non-sealed",
+        "package",
+        "permits",
+        "private",
+        "protected",
+        "public",
+        "record",
+        "return",
+        "sealed",
+        "static",
+        "strictfp",
+        "super",
+        "switch",
+        "synchronized",
+        "this",
+        "throw",
+        "throws",
+        "transient",
+        "try",
+        "var",
+        "volatile",
+        "while",
+        "yield",
+    ],
+    types: &[
+        "boolean", "byte", "char", "double", "float", "int", "long", "short", "void",
+    ],
+    constants: &["true", "false", "null"],
+    capitalized_types: true,
+    nested_comments: false,
+    strings: &[
+        StringSyntax::TripleDouble,
+        StringSyntax::Double,
+        StringSyntax::Char,
+    ],
+    string_prefixes: &[],
+    annotations: AnnotationSyntax::At,
+    preprocessor: false,
+};
+
+pub struct JavaLexer;
+
+impl lexer::Lexer for JavaLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn java() {
+        let rows = highlight(
+            "@Override\npublic String toString() {\n  char c = 'a';\n  return \"\"\"\n    b \"c\"\n    \"\"\" + 10L;\n}",
+            "java",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["@Override"],
+            "should highlight annotations"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["String", "char"],
+            "should highlight built-in and capitalized types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["'a'", "\"\"\"", "    b \"c\"", "    \"\"\""],
+            "should highlight chars and text blocks"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["10L"],
+            "should highlight numbers"
+        );
+    }
+}
@@ -1,11 +1,14 @@
-use crate::{highlighter_tokenizer::HighlighterTokenizer, token::Token};
-use mdxjs::hast;
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, token::Token, token_kind::TokenKind};
 
 pub struct Lexer {
     input: Vec<char>,
     pub position: usize,
     pub read_position: usize,
     pub ch: char,
+    pub jsx: bool,
+    /// Lexes the content of JSX expression containers (`{...}`), so that
+    /// dialects such as TypeScript apply to them too.
+    pub lex_expression: fn(&mut Lexer, &mut HighlighterTokenizer),
 }
 
 fn is_letter(ch: char) -> bool {
@@ -19,6 +22,8 @@ impl Lexer {
             position: 0,
             read_position: 0,
             ch: '\0',
+            jsx: false,
+            lex_expression: tokenize_lexer,
         }
     }
 
@@ -32,6 +37,91 @@ impl Lexer {
         self.read_position += 1;
     }
 
+    pub fn peek_char(&self) -> char {
+        if self.read_position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[self.read_position]
+        }
+    }
+
+    /// Last character before `position` that is not whitespace.
+    pub fn previous_char(&self, position: usize) -> Option<char> {
+        self.input[..position.min(self.input.len())]
+            .iter()
+            .rev()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+    }
+
+    /// Last word before `position`, skipping whitespace.
+    pub fn previous_word(&self, position: usize) -> String {
+        let mut end = position.min(self.input.len());
+        while end > 0 && self.input[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        let mut start = end;
+        while start > 0 && is_letter(self.input[start - 1]) {
+            start -= 1;
+        }
+        self.input[start..end].iter().collect()
+    }
+
+    /// Position of the `}` closing the `{` at `position`.
+    fn find_closing_brace(&self, position: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut index = position;
+        while index < self.input.len() {
+            let ch = self.input[index];
+            if let Some(quote_ch) = quote {
+                if ch == '\\' {
+                    index += 1;
+                } else if ch == quote_ch {
+                    quote = None;
+                }
+            } else {
+                match ch {
+                    '"' | '\'' | '`' => quote = Some(ch),
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(index);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// Whether the `<` at the current position opens a JSX element instead of
+    /// being a comparison or a type argument.
+    fn is_jsx_start(&self) -> bool {
+        let next = self.peek_char();
+        // Closing tags start JSX only on their own, such as the closing tag
+        // of a JSX element in MDX
+        if next == '/' {
+            return self.previous_char(self.position).is_none();
+        }
+        if !is_letter(next) && next != '>' {
+            return false;
+        }
+        match self.previous_char(self.position) {
+            None => true,
+            Some(ch) => {
+                "(,=:?[{};&|!>".contains(ch)
+                    || matches!(
+                        &self.previous_word(self.position)[..],
+                        "return" | "yield" | "default" | "case"
+                    )
+            }
+        }
+    }
+
     pub fn next_token(&mut self) -> Token {
         let read_identifier = |l: &mut Lexer| -> Vec<char> {
             let position = l.position;
@@ -51,9 +141,10 @@ impl Lexer {
                 l.read_char();
             }
             l.read_char();
+            // Strings ending in an escape at the end of the input (`"a\`)
             if l.position > l.input.len() {
-                l.position -= 1;
-                l.read_position -= 1;
+                l.position = l.input.len();
+                l.read_position = l.position + 1;
             }
             l.input[position..l.position].to_vec()
         };
@@ -125,6 +216,14 @@ impl Lexer {
         {
             return Token::COMMENT(read_string(self, '\n'));
         }
+        if self.ch == '@' && is_letter(self.peek_char()) {
+            let position = self.position;
+            self.read_char();
+            while self.position < self.input.len() && (is_letter(self.ch) || self.ch == '.') {
+                self.read_char();
+            }
+            return Token::META(self.input[position..self.position].to_vec());
+        }
 
         match self.ch {
             '\n' => {
@@ -134,7 +233,7 @@ impl Lexer {
                 tok = Token::EOF;
             }
             '0' => {
-                return if self.input[self.read_position] == 'x' {
+                return if self.peek_char() == 'x' {
                     let start_position = self.position;
                     self.read_char();
                     self.read_char();
@@ -177,7 +276,11 @@ impl Lexer {
                                     .append(&mut self.input[position..self.position].to_vec());
                                 return Token::ENTITY(identifier);
                             }
-                            if start_position > 0 && self.input[start_position - 1] == '.' {
+                            // Member access, but not spread (`...a`)
+                            if start_position > 0
+                                && self.input[start_position - 1] == '.'
+                                && (start_position < 2 || self.input[start_position - 2] != '.')
+                            {
                                 return Token::ENTITY(identifier);
                             }
                             if self.ch == '(' {
@@ -247,91 +350,282 @@ pub fn get_keyword_token(identifier: &Vec<char>) -> Result<Token, String> {
     }
 }
 
-pub fn highlight(input: Vec<char>) -> Vec<hast::Node> {
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
     let mut l = Lexer::new(input);
+    l.jsx = true;
     l.read_char();
-    let mut tokens = HighlighterTokenizer::new();
+    tokenize_lexer(&mut l, tokens);
+}
+
+fn tokenize_lexer(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
     loop {
         let token = l.next_token();
         if token == Token::EOF {
-            tokens.end_of_line();
             break;
         }
 
-        match token {
-            Token::INT(value) => {
-                tokens.add_token(value.iter().collect::<String>(), "hl-c".into());
-            }
-            Token::IDENT(value) => {
-                tokens.add_text(value.iter().collect::<String>());
+        add_token(l, token, tokens);
+    }
+}
+
+pub fn add_token(l: &mut Lexer, token: Token, tokens: &mut HighlighterTokenizer) {
+    match token {
+        Token::INT(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Constant);
+        }
+        Token::IDENT(value) => {
+            tokens.add_text(value.iter().collect::<String>());
+        }
+        Token::STRING(value) => {
+            tokens.add_multiline_token(value.iter().collect::<String>(), TokenKind::String);
+        }
+        Token::ENTITY(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Entity);
+        }
+        Token::CONSTANT(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Constant);
+        }
+        Token::KEYWORD(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Keyword);
+        }
+        Token::COMMENT(value) => {
+            tokens.add_multiline_token(value.iter().collect::<String>(), TokenKind::Comment);
+        }
+        Token::VAR(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Var);
+        }
+        Token::TYPE(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Type);
+        }
+        Token::META(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Meta);
+        }
+        Token::ENTITYTAG(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::EntityTag);
+        }
+        Token::ATTRIBUTE(value) => {
+            tokens.add_token(value.iter().collect::<String>(), TokenKind::Attribute);
+        }
+        Token::ENDL(_) => {
+            tokens.end_of_line();
+        }
+        _ => {
+            if l.jsx && l.ch == '<' && l.is_jsx_start() {
+                read_jsx_element(l, tokens);
+                return;
             }
-            Token::STRING(value) => {
-                let mut s = String::new();
-                for ch in value {
-                    if ch == '<' {
-                        s.push_str("&lt;");
-                    } else if ch == '>' {
-                        s.push_str("&gt;");
-                    } else {
-                        s.push(ch);
-                    }
-                }
-                let split = s.split("\n");
-                let split_len = split.clone().collect::<Vec<&str>>().len();
-                let mut index = 0;
-                for val in split {
-                    tokens.add_token(val.into(), "hl-s".into());
-                    index = index + 1;
-                    if index != split_len {
-                        tokens.end_of_line();
-                    }
-                }
+            tokens.add_text(l.ch.to_string());
+            l.read_char();
+        }
+    }
+}
+
+/// Read a JSX element from the `<` of its opening tag to the end of its
+/// closing tag.
+fn read_jsx_element(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    tokens.add_text("<".into());
+    l.read_char();
+    if l.ch == '/' {
+        tokens.add_text("/".into());
+        l.read_char();
+        read_jsx_name(l, tokens);
+        while l.ch != '>' && l.position < l.input.len() {
+            tokens.add_text(l.ch.to_string());
+            l.read_char();
+        }
+        if l.ch == '>' {
+            tokens.add_text(">".into());
+            l.read_char();
+        }
+        return;
+    }
+    if l.ch == '>' {
+        tokens.add_text(">".into());
+        l.read_char();
+        read_jsx_children(l, tokens);
+        return;
+    }
+    read_jsx_name(l, tokens);
+
+    loop {
+        match l.ch {
+            '\0' => return,
+            '\n' => {
+                tokens.end_of_line();
+                l.read_char();
             }
-            Token::ENTITY(value) => {
-                tokens.add_token(value.iter().collect::<String>(), "hl-en".into());
+            '/' if l.peek_char() == '>' => {
+                tokens.add_text("/>".into());
+                l.read_char();
+                l.read_char();
+                return;
             }
-            Token::CONSTANT(value) => {
-                tokens.add_token(value.iter().collect::<String>(), "hl-c".into());
+            '>' => {
+                tokens.add_text(">".into());
+                l.read_char();
+                read_jsx_children(l, tokens);
+                return;
             }
-            Token::KEYWORD(value) => {
-                tokens.add_token(value.iter().collect::<String>(), "hl-k".into());
+            '{' => read_jsx_expression(l, tokens),
+            '"' | '\'' => {
+                let token = l.next_token();
+                add_token(l, token, tokens);
             }
-            Token::COMMENT(value) => {
-                let mut lines = String::new();
-                for ch in value {
-                    if ch == '<' {
-                        lines.push_str("&lt;");
-                    } else if ch == '>' {
-                        lines.push_str("&gt;");
-                    } else {
-                        lines.push(ch);
-                    }
-                }
-                let split = lines.split("\n");
-                let split_len = split.clone().collect::<Vec<&str>>().len();
-                let mut index = 0;
-                for val in split {
-                    if val.len() > 1 {
-                        tokens.add_token(val.into(), "hl-cmt".into());
-                    }
-                    index = index + 1;
-                    if index != split_len {
-                        tokens.end_of_line();
-                    }
+            ch if is_letter(ch) => {
+                let position = l.position;
+                while l.position < l.input.len() && is_jsx_name_char(l.ch) {
+                    l.read_char();
                 }
+                let name = l.input[position..l.position].to_vec();
+                add_token(l, Token::ATTRIBUTE(name), tokens);
             }
-            Token::VAR(value) => {
-                tokens.add_token(value.iter().collect::<String>(), "hl-v".into());
+            ch => {
+                tokens.add_text(ch.to_string());
+                l.read_char();
             }
-            Token::ENDL(_) => {
+        }
+    }
+}
+
+/// Read the children of a JSX element up to the end of its closing tag.
+fn read_jsx_children(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    loop {
+        match l.ch {
+            '\0' => return,
+            '\n' => {
                 tokens.end_of_line();
+                l.read_char();
             }
-            _ => {
-                tokens.add_text(l.ch.to_string());
+            '{' => read_jsx_expression(l, tokens),
+            '<' if l.peek_char() == '/' => {
+                tokens.add_text("</".into());
+                l.read_char();
+                l.read_char();
+                read_jsx_name(l, tokens);
+                while l.ch != '>' && l.position < l.input.len() {
+                    tokens.add_text(l.ch.to_string());
+                    l.read_char();
+                }
+                if l.ch == '>' {
+                    tokens.add_text(">".into());
+                    l.read_char();
+                }
+                return;
+            }
+            '<' => read_jsx_element(l, tokens),
+            ch => {
+                tokens.add_text(ch.to_string());
                 l.read_char();
             }
         }
     }
+}
+
+fn read_jsx_name(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let position = l.position;
+    while l.position < l.input.len() && (is_jsx_name_char(l.ch) || l.ch == '.') {
+        l.read_char();
+    }
+    if l.position > position {
+        let name = l.input[position..l.position].to_vec();
+        // Components are highlighted like other entities, intrinsic elements
+        // as tags
+        let token = if name[0].is_uppercase() || name.contains(&'.') {
+            Token::ENTITY(name)
+        } else {
+            Token::ENTITYTAG(name)
+        };
+        add_token(l, token, tokens);
+    }
+}
+
+/// Read an expression container (`{...}`) and lex its content with
+/// `lex_expression`.
+fn read_jsx_expression(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let start_position = l.position + 1;
+    let end_position = l.find_closing_brace(l.position).unwrap_or(l.input.len());
+    tokens.add_text("{".into());
+
+    let mut expression = Lexer::new(l.input[start_position..end_position].to_vec());
+    expression.jsx = l.jsx;
+    expression.lex_expression = l.lex_expression;
+    expression.read_char();
+    (l.lex_expression)(&mut expression, tokens);
+
+    l.read_position = end_position;
+    l.read_char();
+    if l.ch == '}' {
+        tokens.add_text("}".into());
+        l.read_char();
+    }
+}
+
+fn is_jsx_name_char(ch: char) -> bool {
+    is_letter(ch) || ch.is_numeric() || ch == '-' || ch == ':'
+}
+
+pub struct JavascriptLexer;
+
+impl lexer::Lexer for JavascriptLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::with_class, LanguageRegistry};
+    use pretty_assertions::assert_eq;
 
-    tokens.get_highlighted_code()
+    fn highlight(code: &str) -> Vec<mdxjs::hast::Node> {
+        LanguageRegistry::default().highlight(code.chars().collect(), "jsx")
+    }
+
+    #[test]
+    fn jsx() {
+        let rows = highlight(
+            "const a = (\n  <Tabs.Tab label=\"Don't\" {...props}>\n    <p className='b'>Don't {count}</p>\n    <Icon />\n  </Tabs.Tab>\n);",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["Tabs.Tab", "Icon", "Tabs.Tab"],
+            "should highlight components as entities"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["p", "p"],
+            "should highlight intrinsic elements as tags"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["label", "className"],
+            "should highlight attribute names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"Don't\"", "'b'"],
+            "should highlight attribute values but not text"
+        );
+        assert_eq!(rows.len(), 6, "should keep one row per line");
+    }
+
+    #[test]
+    fn jsx_fragment_and_expressions() {
+        let rows = highlight("<>{items.map((item) => <li key={item.id}>{item.name}</li>)}</>");
+        assert_eq!(with_class(&rows, "hl-ent"), vec!["li", "li"], "should lex nested elements");
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["map", "id", "name"],
+            "should lex expression containers as JavaScript"
+        );
+    }
+
+    #[test]
+    fn comparison() {
+        let rows = highlight("if (a <b && c > d) {}");
+        assert!(
+            with_class(&rows, "hl-ent").is_empty(),
+            "should not lex comparisons as JSX"
+        );
+    }
 }
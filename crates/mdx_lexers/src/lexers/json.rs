@@ -0,0 +1,523 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+/// Flavour of JSON.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsonDialect {
+    /// Strict JSON.
+    Json,
+    /// JSON with comments and trailing commas.
+    Jsonc,
+    /// JSON5: JSONC with unquoted keys, single quotes, and more numbers.
+    Json5,
+}
+
+/// What the lexer expects next in the structure of the document.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Expect {
+    /// A value, at the start or after `:` or `,` in an array.
+    Value,
+    /// A value or `]`, after `[`.
+    ValueOrEnd,
+    /// A key, after `,` in an object.
+    Key,
+    /// A key or `}`, after `{`.
+    KeyOrEnd,
+    Colon,
+    /// `,` or the end of the object or array, after a value.
+    CommaOrEnd,
+    /// Nothing, after the value of the document.
+    Nothing,
+}
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    pub dialect: JsonDialect,
+    /// Whether invalid tokens are errors instead of their regular kind.
+    pub flag_errors: bool,
+    expect: Expect,
+    /// Open objects (`{`) and arrays (`[`).
+    stack: Vec<char>,
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            dialect: JsonDialect::Json,
+            flag_errors: false,
+            expect: Expect::Value,
+            stack: vec![],
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Read a string, which ends at the end of the line when it is not closed.
+    ///
+    /// Returns whether the string is closed.
+    fn read_string(&mut self) -> (Vec<char>, bool) {
+        let position = self.position;
+        let quote = self.ch;
+        self.read_char();
+        while self.position < self.input.len() && self.ch != quote && self.ch != '\n' {
+            if self.ch == '\\' {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        let closed = self.ch == quote && self.position < self.input.len();
+        if closed {
+            self.read_char();
+        }
+        (
+            self.input[position..self.position.min(self.input.len())].to_vec(),
+            closed,
+        )
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        if matches!(self.ch, '-' | '+') {
+            self.read_char();
+        }
+        while self.position < self.input.len()
+            && (self.ch.is_alphanumeric() || self.ch == '.' || self.ch == '_')
+        {
+            // Exponent sign
+            if matches!(self.ch, 'e' | 'E')
+                && matches!(self.peek_char(1), '+' | '-')
+                && !self.input[position..self.position]
+                    .iter()
+                    .any(|ch| matches!(ch, 'x' | 'X'))
+            {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether a number starts at the current position.
+    fn is_number_start(&self) -> bool {
+        let ch = if matches!(self.ch, '-' | '+') {
+            self.peek_char(1)
+        } else {
+            self.ch
+        };
+        ch.is_ascii_digit() || (ch == '.' && self.peek_char(1).is_ascii_digit())
+    }
+
+    /// Next character after `position` that is not whitespace.
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Whether the string or name ending at the current position is a key.
+    fn is_key(&self) -> bool {
+        matches!(self.expect, Expect::Key | Expect::KeyOrEnd)
+            || (self.expect != Expect::Value && self.next_char(self.position) == ':')
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+        if self.ch == '/' && self.peek_char(1) == '/' {
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            let comment = self.input[position..self.position].to_vec();
+            return self.check_comment(comment);
+        }
+        if self.ch == '/' && self.peek_char(1) == '*' {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len() && !(self.ch == '*' && self.peek_char(1) == '/')
+            {
+                self.read_char();
+            }
+            self.read_char();
+            self.read_char();
+            let comment = self.input[position..self.position.min(self.input.len())].to_vec();
+            return self.check_comment(comment);
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '{' | '[' => {
+                let valid = self.value_start();
+                self.stack.push(self.ch);
+                self.expect = if self.ch == '{' {
+                    Expect::KeyOrEnd
+                } else {
+                    Expect::ValueOrEnd
+                };
+                self.read_char();
+                self.punctuation(position, valid)
+            }
+            '}' | ']' => {
+                let open = if self.ch == '}' { '{' } else { '[' };
+                let trailing_comma = self.dialect != JsonDialect::Json
+                    && self.expect
+                        == if open == '{' {
+                            Expect::Key
+                        } else {
+                            Expect::Value
+                        };
+                let valid = self.stack.last() == Some(&open)
+                    && (matches!(
+                        self.expect,
+                        Expect::CommaOrEnd | Expect::KeyOrEnd | Expect::ValueOrEnd
+                    ) || trailing_comma);
+                if self.stack.last() == Some(&open) {
+                    self.stack.pop();
+                    self.value_end();
+                }
+                self.read_char();
+                self.punctuation(position, valid)
+            }
+            ',' => {
+                let valid = self.expect == Expect::CommaOrEnd;
+                if valid {
+                    self.expect = if self.stack.last() == Some(&'{') {
+                        Expect::Key
+                    } else {
+                        Expect::Value
+                    };
+                }
+                self.read_char();
+                self.punctuation(position, valid)
+            }
+            ':' => {
+                let valid = self.expect == Expect::Colon;
+                self.expect = Expect::Value;
+                self.read_char();
+                self.punctuation(position, valid)
+            }
+            '"' | '\'' => {
+                let single_quote = self.ch == '\'';
+                let (value, closed) = self.read_string();
+                let valid = closed && (!single_quote || self.dialect == JsonDialect::Json5);
+                if self.is_key() {
+                    let valid = valid && matches!(self.expect, Expect::Key | Expect::KeyOrEnd);
+                    self.expect = Expect::Colon;
+                    self.check(Token::ATTRIBUTE(value), valid)
+                } else {
+                    let valid = valid && self.value_start();
+                    self.value_end();
+                    self.check(Token::STRING(value), valid)
+                }
+            }
+            _ if self.is_number_start() => {
+                let value = self.read_number();
+                let valid = self.value_start() && self.is_valid_number(&value);
+                self.value_end();
+                self.check(Token::INT(value), valid)
+            }
+            ch if is_identifier_char(ch) => {
+                while self.position < self.input.len() && is_identifier_char(self.ch) {
+                    self.read_char();
+                }
+                let value = self.input[position..self.position].to_vec();
+                let name: String = value.iter().collect();
+                if self.is_key() {
+                    let valid = self.dialect == JsonDialect::Json5
+                        && matches!(self.expect, Expect::Key | Expect::KeyOrEnd);
+                    self.expect = Expect::Colon;
+                    return self.check(Token::ATTRIBUTE(value), valid);
+                }
+                let valid = self.value_start()
+                    && match &name[..] {
+                        "true" | "false" | "null" => true,
+                        "Infinity" | "NaN" => self.dialect == JsonDialect::Json5,
+                        _ => false,
+                    };
+                self.value_end();
+                match &name[..] {
+                    "true" | "false" | "null" | "Infinity" | "NaN" => {
+                        self.check(Token::CONSTANT(value), valid)
+                    }
+                    _ => self.check(Token::IDENT(value), valid),
+                }
+            }
+            ch => {
+                self.read_char();
+                // `-Infinity`, `+NaN`
+                let valid = matches!(ch, '-' | '+') && self.dialect == JsonDialect::Json5;
+                self.check(Token::IDENT(vec![ch]), valid)
+            }
+        }
+    }
+
+    /// Whether a value can start here.
+    fn value_start(&self) -> bool {
+        matches!(self.expect, Expect::Value | Expect::ValueOrEnd)
+    }
+
+    /// Update what comes next after a value.
+    fn value_end(&mut self) {
+        self.expect = if self.stack.is_empty() {
+            Expect::Nothing
+        } else {
+            Expect::CommaOrEnd
+        };
+    }
+
+    fn is_valid_number(&self, value: &[char]) -> bool {
+        let number: String = value.iter().collect();
+        let unsigned = number.strip_prefix('-').unwrap_or(&number);
+        if self.dialect == JsonDialect::Json5 {
+            let unsigned = unsigned.strip_prefix('+').unwrap_or(unsigned);
+            if let Some(hex) = unsigned
+                .strip_prefix("0x")
+                .or_else(|| unsigned.strip_prefix("0X"))
+            {
+                return !hex.is_empty() && hex.chars().all(|ch| ch.is_ascii_hexdigit());
+            }
+            return !unsigned.contains('_')
+                && unsigned.parse::<f64>().is_ok()
+                && unsigned
+                    .chars()
+                    .all(|ch| !ch.is_alphabetic() || matches!(ch, 'e' | 'E'));
+        }
+
+        // -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+        let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+            Some(index) => (&unsigned[..index], Some(&unsigned[index + 1..])),
+            None => (unsigned, None),
+        };
+        let (integer, fraction) = match mantissa.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (mantissa, None),
+        };
+        let digits = |value: &str| !value.is_empty() && value.chars().all(|ch| ch.is_ascii_digit());
+        digits(integer)
+            && (integer == "0" || !integer.starts_with('0'))
+            && fraction.map(digits).unwrap_or(true)
+            && exponent
+                .map(|exponent| digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent)))
+                .unwrap_or(true)
+    }
+
+    fn punctuation(&self, position: usize, valid: bool) -> Token {
+        self.check(
+            Token::PUNCTUATION(self.input[position..self.position].to_vec()),
+            valid,
+        )
+    }
+
+    fn check_comment(&self, comment: Vec<char>) -> Token {
+        self.check(Token::COMMENT(comment), self.dialect != JsonDialect::Json)
+    }
+
+    /// Turn `token` into an error when it is not valid and errors are
+    /// flagged.
+    fn check(&self, token: Token, valid: bool) -> Token {
+        if valid || !self.flag_errors {
+            return token;
+        }
+        match token {
+            Token::PUNCTUATION(value)
+            | Token::COMMENT(value)
+            | Token::ATTRIBUTE(value)
+            | Token::STRING(value)
+            | Token::INT(value)
+            | Token::CONSTANT(value)
+            | Token::IDENT(value) => Token::ERROR(value),
+            token => token,
+        }
+    }
+}
+
+pub fn tokenize(
+    input: Vec<char>,
+    tokens: &mut HighlighterTokenizer,
+    dialect: JsonDialect,
+    flag_errors: bool,
+) {
+    let mut l = Lexer::new(input);
+    l.dialect = dialect;
+    l.flag_errors = flag_errors;
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct JsonLexer {
+    pub dialect: JsonDialect,
+    /// Whether to highlight invalid code with the error class name.
+    pub flag_errors: bool,
+}
+
+impl lexer::Lexer for JsonLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.dialect, self.flag_errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::{highlight, with_class}, LanguageRegistry};
+    use pretty_assertions::assert_eq;
+
+    fn highlight_errors(code: &str, dialect: JsonDialect) -> Vec<mdxjs::hast::Node> {
+        let mut registry = LanguageRegistry::new();
+        registry.register(
+            &["json"],
+            JsonLexer {
+                dialect,
+                flag_errors: true,
+            },
+        );
+        registry.highlight(code.chars().collect(), "json")
+    }
+
+    #[test]
+    fn json() {
+        let rows = highlight(
+            "{\n  \"name\": \"mdx\",\n  \"tags\": [\"a\", 1.5e3, -2],\n  \"ok\": true,\n  \"x\": null\n}",
+            "json",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["\"name\"", "\"tags\"", "\"ok\"", "\"x\""],
+            "should highlight keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"mdx\"", "\"a\""],
+            "should highlight string values"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1.5e3", "-2", "true", "null"],
+            "should highlight numbers and literals"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p").concat(),
+            "{:,:[,,],:,:}",
+            "should highlight punctuation"
+        );
+        assert!(
+            with_class(&rows, "hl-err").is_empty(),
+            "should not flag errors by default"
+        );
+
+        let rows = highlight("{\"a\": 1,}", "json");
+        assert!(
+            with_class(&rows, "hl-err").is_empty(),
+            "should not flag errors by default (2)"
+        );
+    }
+
+    #[test]
+    fn json5() {
+        let rows = highlight(
+            "// Config\n{\n  unquoted: 'single', /* inline */\n  hex: 0xFF, n: .5, m: +Infinity,\n  list: [1, 2,],\n}",
+            "json5",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["// Config", "/* inline */"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["unquoted", "hex", "n", "m", "list"],
+            "should highlight unquoted keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["'single'"],
+            "should highlight single quotes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["0xFF", ".5", "Infinity", "1", "2"],
+            "should highlight numbers"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let valid = "{\"a\": [1, {\"b\": -0.5e-3}], \"c\": \"\\\"d\"}";
+        let rows = highlight_errors(valid, JsonDialect::Json);
+        assert!(
+            with_class(&rows, "hl-err").is_empty(),
+            "should not flag valid JSON"
+        );
+
+        let rows = highlight_errors(
+            "// a\n{\"a\": 01, b: 'c', \"d\": [1,], \"e\" 2, \"f\": undefined}}",
+            JsonDialect::Json,
+        );
+        assert_eq!(
+            with_class(&rows, "hl-err"),
+            vec!["// a", "01", "b", "'c'", "]", "2", "undefined", "}"],
+            "should flag invalid JSON"
+        );
+
+        let rows = highlight_errors("// a\n{\"a\": [1,], \"b\": 2,}", JsonDialect::Jsonc);
+        assert!(
+            with_class(&rows, "hl-err").is_empty(),
+            "should support comments and trailing commas in JSONC"
+        );
+
+        let rows = highlight_errors(
+            "{a: 'b', c: 0x1F, d: -Infinity, e: 'f\n}",
+            JsonDialect::Json5,
+        );
+        assert_eq!(
+            with_class(&rows, "hl-err"),
+            vec!["'f"],
+            "should flag unterminated strings in JSON5"
+        );
+    }
+}
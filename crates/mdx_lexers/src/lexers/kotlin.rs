@@ -0,0 +1,132 @@
+//! Lexer for Kotlin.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "abstract",
+        "annotation",
+        "as",
+        "break",
+        "by",
+        "catch",
+        "class",
+        "companion",
+        "const",
+        "constructor",
+        "continue",
+        "crossinline",
+        "data",
+        "do",
+        "else",
+        "enum",
+        "external",
+        "final",
+        "finally",
+        "for",
+        "fun",
+        "get",
+        "if",
+        "import",
+        "in",
+        "infix",
+        "init",
+        "inline",
+        "inner",
+        "interface",
+        "internal",
+        "is",
+        "lateinit",
+        "noinline",
+        "object",
+        "open",
+        "operator",
+        "out",
+        "override",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "reified",
+        "return",
+        "sealed",
+        "set",
+        "super",
+        "suspend",
+        "tailrec",
+        "this",
+        "throw",
+        "try",
+        "typealias",
+        "val",
+        "value",
+        "var",
+        "vararg",
+        "when",
+        "where",
+        "while",
+    ],
+    types: &[],
+    constants: &["true", "false", "null"],
+    capitalized_types: true,
+    nested_comments: true,
+    strings: &[
+        StringSyntax::TripleDouble,
+        StringSyntax::Double,
+        StringSyntax::Char,
+    ],
+    string_prefixes: &[],
+    annotations: AnnotationSyntax::At,
+    preprocessor: false,
+};
+
+pub struct KotlinLexer;
+
+impl lexer::Lexer for KotlinLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kotlin() {
+        let rows = highlight(
+            "@file:JvmName(\"A\")\n/* a /* b */ */\nfun greet(name: String?): Int {\n  val raw = \"\"\"a \"b\" c\"\"\"\n  return 0\n}",
+            "kt",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["@file:JvmName"],
+            "should highlight annotations with use-site targets"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["/* a /* b */ */"],
+            "should highlight nested comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["fun", "val", "return"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["String", "Int"],
+            "should highlight capitalized types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"A\"", "\"\"\"a \"b\" c\"\"\""],
+            "should highlight strings"
+        );
+    }
+}
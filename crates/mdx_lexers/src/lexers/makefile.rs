@@ -0,0 +1,393 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+const DIRECTIVES: [&str; 17] = [
+    "define", "endef", "else", "endif", "export", "ifdef", "ifeq", "ifndef", "ifneq", "include",
+    "-include", "sinclude", "override", "private", "undefine", "unexport", "vpath",
+];
+
+const FUNCTIONS: [&str; 37] = [
+    "abspath",
+    "addprefix",
+    "addsuffix",
+    "and",
+    "basename",
+    "call",
+    "dir",
+    "error",
+    "eval",
+    "file",
+    "filter",
+    "filter-out",
+    "findstring",
+    "firstword",
+    "flavor",
+    "foreach",
+    "if",
+    "info",
+    "join",
+    "lastword",
+    "let",
+    "notdir",
+    "or",
+    "origin",
+    "patsubst",
+    "realpath",
+    "shell",
+    "sort",
+    "strip",
+    "subst",
+    "suffix",
+    "value",
+    "warning",
+    "wildcard",
+    "word",
+    "wordlist",
+    "words",
+];
+
+/// Automatic variables (`$@`, `$(@D)`).
+const AUTOMATIC_VARIABLES: &str = "@%<?^+|*";
+
+/// Add `input` as text, one row per line.
+fn add_text(tokens: &mut HighlighterTokenizer, input: &[char]) {
+    for ch in input {
+        let token = if *ch == '\n' {
+            Token::ENDL('\n')
+        } else {
+            Token::CH(*ch)
+        };
+        lexers::add_token(tokens, token);
+    }
+}
+
+/// Position of the parenthesis or brace closing the one at `position`, or
+/// the end of `input`.
+fn find_closing(input: &[char], position: usize) -> usize {
+    let open = input[position];
+    let close = if open == '(' { ')' } else { '}' };
+    let mut depth = 0;
+    for (index, ch) in input.iter().enumerate().skip(position) {
+        if *ch == open {
+            depth += 1;
+        } else if *ch == close {
+            depth -= 1;
+            if depth == 0 {
+                return index;
+            }
+        }
+    }
+    input.len()
+}
+
+/// Lex text with references to variables (`$(CC)`, `$@`) and calls of
+/// functions (`$(wildcard *.c)`); `#` starts a comment if `comments`.
+fn tokenize_text(input: &[char], tokens: &mut HighlighterTokenizer, comments: bool) {
+    let mut text_start = 0;
+    let mut index = 0;
+    while index < input.len() {
+        let ch = input[index];
+        let next = input.get(index + 1).copied().unwrap_or('\0');
+        if comments && ch == '#' && (index == 0 || input[index - 1] != '\\') {
+            add_text(tokens, &input[text_start..index]);
+            lexers::add_token(tokens, Token::COMMENT(input[index..].to_vec()));
+            return;
+        }
+        if ch != '$' || next == '$' || next == '\0' {
+            index += if ch == '$' { 2 } else { 1 };
+            continue;
+        }
+
+        add_text(tokens, &input[text_start..index]);
+        if next == '(' || next == '{' {
+            let end = find_closing(input, index + 1);
+            let inner = &input[index + 2..end];
+            let name_length = inner
+                .iter()
+                .take_while(|ch| !ch.is_whitespace() && **ch != ',')
+                .count();
+            let name: String = inner[..name_length].iter().collect();
+            let end = (end + 1).min(input.len());
+            if FUNCTIONS.contains(&&name[..]) && name_length < inner.len() {
+                add_text(tokens, &input[index..index + 2]);
+                lexers::add_token(tokens, Token::ENTITY(inner[..name_length].to_vec()));
+                tokenize_text(&inner[name_length..], tokens, false);
+                add_text(tokens, &input[index + 2 + inner.len()..end]);
+            } else if inner.len() == 2
+                && AUTOMATIC_VARIABLES.contains(inner[0])
+                && matches!(inner[1], 'D' | 'F')
+            {
+                lexers::add_token(tokens, Token::CONSTANT(input[index..end].to_vec()));
+            } else {
+                lexers::add_token(tokens, Token::VAR(input[index..end].to_vec()));
+            }
+            index = end;
+        } else if AUTOMATIC_VARIABLES.contains(next) {
+            lexers::add_token(tokens, Token::CONSTANT(input[index..index + 2].to_vec()));
+            index += 2;
+        } else {
+            lexers::add_token(tokens, Token::VAR(input[index..index + 2].to_vec()));
+            index += 2;
+        }
+        text_start = index;
+    }
+    add_text(tokens, &input[text_start..]);
+}
+
+/// Lex the targets or prerequisites of a rule, making names tokens of
+/// `kind`.
+fn tokenize_names(input: &[char], tokens: &mut HighlighterTokenizer, kind: fn(Vec<char>) -> Token) {
+    let mut index = 0;
+    while index < input.len() {
+        let start = index;
+        let is_space = input[index].is_whitespace();
+        while index < input.len() && input[index].is_whitespace() == is_space {
+            index += 1;
+        }
+        let word = &input[start..index];
+        if is_space || word == ['|'] {
+            add_text(tokens, word);
+        } else if word.contains(&'$') {
+            tokenize_text(word, tokens, false);
+        } else if word[0] == '.' && word[1..].iter().all(|ch| ch.is_ascii_uppercase()) {
+            // Special targets (`.PHONY`)
+            lexers::add_token(tokens, Token::KEYWORD(word.to_vec()));
+        } else {
+            lexers::add_token(tokens, kind(word.to_vec()));
+        }
+    }
+}
+
+/// Operator of an assignment (`=`, `:=`, `+=`) or rule (`:`, `::`) in
+/// `line`, as its range and whether it is an assignment.
+fn find_operator(line: &[char]) -> Option<(usize, usize, bool)> {
+    let mut depth = 0;
+    for (index, ch) in line.iter().enumerate() {
+        let next = line.get(index + 1).copied().unwrap_or('\0');
+        match ch {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            '#' => return None,
+            _ if depth > 0 => {}
+            '=' => {
+                let start = if index > 0 && "?+!".contains(line[index - 1]) {
+                    index - 1
+                } else {
+                    index
+                };
+                return Some((start, index + 1, true));
+            }
+            ':' if next == '=' => return Some((index, index + 2, true)),
+            ':' if next == ':' && line.get(index + 2) == Some(&'=') => {
+                return Some((index, index + 3, true))
+            }
+            ':' if next == ':' => return Some((index, index + 2, false)),
+            ':' => return Some((index, index + 1, false)),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Lex a line that is not a recipe; returns whether it starts a `define`.
+fn tokenize_line(line: &[char], tokens: &mut HighlighterTokenizer) -> bool {
+    let indent = line.iter().take_while(|ch| ch.is_whitespace()).count();
+    add_text(tokens, &line[..indent]);
+    let line = &line[indent..];
+    if line.first() == Some(&'#') {
+        lexers::add_token(tokens, Token::COMMENT(line.to_vec()));
+        return false;
+    }
+
+    let word_length = line.iter().take_while(|ch| !ch.is_whitespace()).count();
+    let word: String = line[..word_length].iter().collect();
+    if DIRECTIVES.contains(&&word[..]) && find_operator(&line[..word_length]).is_none() {
+        lexers::add_token(tokens, Token::KEYWORD(line[..word_length].to_vec()));
+        let rest = &line[word_length..];
+        return match &word[..] {
+            "define" => {
+                let space = rest.iter().take_while(|ch| ch.is_whitespace()).count();
+                let name = rest[space..]
+                    .iter()
+                    .take_while(|ch| !ch.is_whitespace())
+                    .count();
+                add_text(tokens, &rest[..space]);
+                lexers::add_token(tokens, Token::VAR(rest[space..space + name].to_vec()));
+                tokenize_text(&rest[space + name..], tokens, true);
+                true
+            }
+            // Assignments (`export A = b`)
+            "export" | "override" | "private" | "unexport" => tokenize_line(rest, tokens),
+            _ => {
+                tokenize_text(rest, tokens, true);
+                false
+            }
+        };
+    }
+
+    match find_operator(line) {
+        Some((start, end, true)) => {
+            let name = line[..start]
+                .iter()
+                .rev()
+                .take_while(|ch| ch.is_whitespace())
+                .count();
+            tokenize_names(&line[..start - name], tokens, Token::VAR);
+            add_text(tokens, &line[start - name..start]);
+            lexers::add_token(tokens, Token::PUNCTUATION(line[start..end].to_vec()));
+            tokenize_text(&line[end..], tokens, true);
+        }
+        Some((start, end, false)) => {
+            tokenize_names(&line[..start], tokens, Token::ENTITY);
+            lexers::add_token(tokens, Token::PUNCTUATION(line[start..end].to_vec()));
+            let rest = &line[end..];
+            if let Some((_, _, true)) = find_operator(rest) {
+                // Variables of targets (`a: B = c`)
+                tokenize_line(rest, tokens);
+            } else {
+                // Recipes on the line of their rule (`a: b ; c`)
+                let recipe = rest.iter().position(|ch| *ch == ';' || *ch == '#');
+                match recipe {
+                    Some(position) if rest[position] == ';' => {
+                        tokenize_names(&rest[..position], tokens, Token::ATTRIBUTE);
+                        add_text(tokens, &rest[position..position + 1]);
+                        tokenize_text(&rest[position + 1..], tokens, false);
+                    }
+                    Some(position) => {
+                        tokenize_names(&rest[..position], tokens, Token::ATTRIBUTE);
+                        lexers::add_token(tokens, Token::COMMENT(rest[position..].to_vec()));
+                    }
+                    None => tokenize_names(rest, tokens, Token::ATTRIBUTE),
+                }
+            }
+        }
+        None => tokenize_text(line, tokens, true),
+    }
+    false
+}
+
+/// Lex a recipe: its prefixes (`@`, `-`, `+`) and command.
+fn tokenize_recipe(line: &[char], tokens: &mut HighlighterTokenizer) {
+    let indent = line.iter().take_while(|ch| ch.is_whitespace()).count();
+    add_text(tokens, &line[..indent]);
+    let prefixes = line[indent..]
+        .iter()
+        .take_while(|ch| matches!(ch, '@' | '-' | '+'))
+        .count();
+    if prefixes > 0 {
+        lexers::add_token(
+            tokens,
+            Token::META(line[indent..indent + prefixes].to_vec()),
+        );
+    }
+    tokenize_text(&line[indent + prefixes..], tokens, false);
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let lines: Vec<&[char]> = input.split(|ch| *ch == '\n').collect();
+    let mut in_define = false;
+    let mut index = 0;
+    while index < lines.len() {
+        if index > 0 {
+            tokens.end_of_line();
+        }
+        let is_recipe = lines[index].first() == Some(&'\t');
+        // Lines continued with `\` on the next lines
+        let mut line = lines[index].to_vec();
+        while line.last() == Some(&'\\') && index + 1 < lines.len() {
+            index += 1;
+            line.push('\n');
+            line.extend_from_slice(lines[index]);
+        }
+
+        if in_define {
+            let trimmed: String = line.iter().collect();
+            if trimmed.trim_start().starts_with("endef") {
+                in_define = tokenize_line(&line, tokens);
+            } else {
+                tokenize_text(&line, tokens, false);
+            }
+        } else if is_recipe {
+            tokenize_recipe(&line, tokens);
+        } else {
+            in_define = tokenize_line(&line, tokens);
+        }
+        index += 1;
+    }
+}
+
+pub struct MakefileLexer;
+
+impl lexer::Lexer for MakefileLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn makefile() {
+        let rows = highlight(
+            "# Build\nCC ?= gcc\nSRC := $(wildcard src/*.c) \\\n  main.c\n.PHONY: all\n\nall: app | build # default\n\napp: $(SRC)\n\t@echo \"$@ from $<\" $$HOME\n\t-$(CC) -o $(@D)/app $^\nifeq ($(CC),gcc)\nexport FLAGS = -O2\nendif",
+            "makefile",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# Build", "# default"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["CC", "SRC", "$(SRC)", "$(CC)", "$(CC)", "FLAGS"],
+            "should highlight variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["$@", "$<", "$(@D)", "$^"],
+            "should highlight automatic variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["wildcard", "all", "app"],
+            "should highlight targets"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["all", "app", "build"],
+            "should highlight prerequisites"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec![".PHONY", "ifeq", "export", "endif"],
+            "should highlight special targets and directives"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["?=", ":=", ":", ":", ":", "="],
+            "should highlight operators"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["@", "-"],
+            "should highlight prefixes of recipes"
+        );
+    }
+
+    #[test]
+    fn define() {
+        let rows = highlight("define RUN\n\t$(CC): a\nendef", "make");
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["define", "endef"],
+            "should highlight directives"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["RUN", "$(CC)"],
+            "should not lex the body of variables as rules"
+        );
+    }
+}
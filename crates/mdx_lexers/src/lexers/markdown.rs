@@ -0,0 +1,447 @@
+//! Lexer for Markdown and MDX.
+//!
+//! Constructs are found with the `markdown` parser rather than by hand, so
+//! that highlighting matches how documents are compiled. Fenced code,
+//! frontmatter, HTML and, in MDX, ESM, expressions and JSX tags are handed to
+//! the lexers registered for their language.
+
+use ::markdown::{mdast::Node, to_mdast, MdxSignal, ParseOptions};
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, registry::LanguageRegistry,
+    token::Token,
+};
+
+/// How to highlight a character.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Paint {
+    Text,
+    Heading,
+    Emphasis,
+    Strong,
+    Delete,
+    InlineCode,
+    /// Links, images and definitions.
+    Link,
+    /// Heading, list, quote and fence markers.
+    Marker,
+    /// Info of fenced code (`ts {1}`).
+    Info,
+    /// Code lexed by the lexer of the language at this index.
+    Language(usize),
+}
+
+struct Painter<'a> {
+    chars: &'a [char],
+    /// Index of the character at each byte offset of the input.
+    offsets: Vec<usize>,
+    paint: Vec<Paint>,
+    languages: Vec<String>,
+}
+
+impl<'a> Painter<'a> {
+    fn new(chars: &'a [char]) -> Self {
+        let mut offsets = vec![];
+        for (index, ch) in chars.iter().enumerate() {
+            for _ in 0..ch.len_utf8() {
+                offsets.push(index);
+            }
+        }
+        offsets.push(chars.len());
+        Self {
+            chars,
+            offsets,
+            paint: vec![Paint::Text; chars.len()],
+            languages: vec![],
+        }
+    }
+
+    /// Range of the characters of `node`.
+    fn range(&self, node: &Node) -> Option<(usize, usize)> {
+        let position = node.position()?;
+        Some((
+            self.offsets[position.start.offset],
+            self.offsets[position.end.offset],
+        ))
+    }
+
+    fn fill(&mut self, start: usize, end: usize, paint: Paint) {
+        for index in start..end.min(self.chars.len()) {
+            self.paint[index] = paint;
+        }
+    }
+
+    /// Hand the code between `start` and `end`, without surrounding
+    /// whitespace, to the lexer of `language`.
+    fn delegate(&mut self, mut start: usize, mut end: usize, language: &str) {
+        while start < end && self.chars[start].is_whitespace() {
+            start += 1;
+        }
+        while end > start && self.chars[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        if start < end {
+            self.languages.push(language.into());
+            self.fill(start, end, Paint::Language(self.languages.len() - 1));
+        }
+    }
+
+    /// Position of the line ending after `position`, or `end`.
+    fn line_end(&self, position: usize, end: usize) -> usize {
+        (position..end)
+            .find(|index| self.chars[*index] == '\n')
+            .unwrap_or(end)
+    }
+
+    /// Position of the start of the last line before `end`, if the range
+    /// spans lines.
+    fn last_line_start(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end)
+            .rev()
+            .find(|index| self.chars[*index] == '\n')
+            .map(|index| index + 1)
+    }
+
+    /// Paint the characters between `start` and `end` as markers, without
+    /// surrounding whitespace.
+    fn marker(&mut self, mut start: usize, mut end: usize) {
+        while start < end && self.chars[start].is_whitespace() {
+            start += 1;
+        }
+        while end > start && self.chars[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        self.fill(start, end, Paint::Marker);
+    }
+
+    /// Fenced code, with its content lexed as its language.
+    fn code(&mut self, start: usize, end: usize, lang: Option<&str>, value: &str) {
+        let fence = self.chars[start];
+        if fence != '`' && fence != '~' {
+            return;
+        }
+        let size = self.chars[start..end]
+            .iter()
+            .take_while(|ch| **ch == fence)
+            .count();
+        let open_end = self.line_end(start, end);
+        self.fill(start, start + size, Paint::Marker);
+        let info_start = (start + size..open_end)
+            .find(|index| !self.chars[*index].is_whitespace())
+            .unwrap_or(open_end);
+        self.fill(info_start, open_end, Paint::Info);
+
+        let mut content_end = end;
+        if let Some(close_start) = self.last_line_start(open_end, end) {
+            let close: String = self.chars[close_start..end].iter().collect();
+            let close = close.trim();
+            if close.chars().count() >= size && close.chars().all(|ch| ch == fence) {
+                self.marker(close_start, end);
+                content_end = close_start - 1;
+            }
+        }
+        // Code in containers (such as lists) has an indent that is not code
+        let content_start = open_end + 1;
+        if let Some(lang) = lang {
+            if content_start < content_end
+                && self.chars[content_start..content_end]
+                    .iter()
+                    .collect::<String>()
+                    == value
+            {
+                self.languages.push(lang.into());
+                self.fill(
+                    content_start,
+                    content_end,
+                    Paint::Language(self.languages.len() - 1),
+                );
+            }
+        }
+    }
+
+    /// Frontmatter, with its fences as markers and its content lexed as
+    /// `language`.
+    fn frontmatter(&mut self, start: usize, end: usize, language: &str) {
+        let open_end = self.line_end(start, end);
+        self.marker(start, open_end);
+        match self.last_line_start(open_end, end) {
+            Some(close_start) => {
+                self.marker(close_start, end);
+                self.delegate(open_end, close_start, language);
+            }
+            None => self.delegate(open_end, end, language),
+        }
+    }
+
+    fn visit(&mut self, node: &Node) {
+        if let Some((start, end)) = self.range(node) {
+            match node {
+                Node::Heading(_) => {
+                    self.fill(start, end, Paint::Heading);
+                    if self.chars[start] == '#' {
+                        let size = self.chars[start..end]
+                            .iter()
+                            .take_while(|ch| **ch == '#')
+                            .count();
+                        self.fill(start, start + size, Paint::Marker);
+                    } else if let Some(underline) = self.last_line_start(start, end) {
+                        self.marker(underline, end);
+                    }
+                }
+                Node::Emphasis(_) => self.fill(start, end, Paint::Emphasis),
+                Node::Strong(_) => self.fill(start, end, Paint::Strong),
+                Node::Delete(_) => self.fill(start, end, Paint::Delete),
+                Node::InlineCode(_) => self.fill(start, end, Paint::InlineCode),
+                Node::Link(_)
+                | Node::Image(_)
+                | Node::LinkReference(_)
+                | Node::ImageReference(_)
+                | Node::Definition(_) => self.fill(start, end, Paint::Link),
+                Node::ThematicBreak(_) => self.marker(start, end),
+                Node::ListItem(item) => {
+                    let mut marker_end = start;
+                    while marker_end < end && !self.chars[marker_end].is_whitespace() {
+                        marker_end += 1;
+                    }
+                    // Checkboxes of tasks (`- [x]`)
+                    if item.checked.is_some() {
+                        while marker_end < end && self.chars[marker_end] != ']' {
+                            marker_end += 1;
+                        }
+                        marker_end += 1;
+                    }
+                    self.marker(start, marker_end);
+                }
+                Node::Blockquote(_) => {
+                    let mut line_start = start;
+                    while line_start < end {
+                        let mut index = line_start;
+                        while index < end && matches!(self.chars[index], ' ' | '\t') {
+                            index += 1;
+                        }
+                        if index < end && self.chars[index] == '>' {
+                            self.fill(index, index + 1, Paint::Marker);
+                        }
+                        line_start = self.line_end(line_start, end) + 1;
+                    }
+                }
+                Node::Code(code) => self.code(start, end, code.lang.as_deref(), &code.value),
+                Node::Html(_) => self.delegate(start, end, "html"),
+                Node::Yaml(_) => self.frontmatter(start, end, "yaml"),
+                Node::Toml(_) => self.frontmatter(start, end, "toml"),
+                Node::MdxjsEsm(_) => self.delegate(start, end, "jsx"),
+                Node::MdxFlowExpression(_) | Node::MdxTextExpression(_) => {
+                    self.fill(start, start + 1, Paint::Marker);
+                    self.fill(end - 1, end, Paint::Marker);
+                    self.delegate(start + 1, end - 1, "jsx");
+                }
+                Node::MdxJsxFlowElement(_) | Node::MdxJsxTextElement(_) => {
+                    // Tags are what is around the children
+                    let children: Vec<(usize, usize)> = node
+                        .children()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|child| self.range(child))
+                        .collect();
+                    match (children.first(), children.last()) {
+                        (Some((first, _)), Some((_, last))) => {
+                            self.delegate(start, *first, "jsx");
+                            self.delegate(*last, end, "jsx");
+                        }
+                        _ => self.delegate(start, end, "jsx"),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(children) = node.children() {
+            for child in children {
+                self.visit(child);
+            }
+        }
+    }
+}
+
+/// Parse `value` as MDX or Markdown, with frontmatter and GFM constructs.
+fn parse(value: &str, mdx: bool) -> Option<Node> {
+    let mut options = if mdx {
+        ParseOptions {
+            // ESM is only found with a parser, which can accept anything as
+            // it is lexed rather than compiled
+            mdx_esm_parse: Some(Box::new(|_| MdxSignal::Ok)),
+            ..ParseOptions::mdx()
+        }
+    } else {
+        ParseOptions::gfm()
+    };
+    options.constructs.frontmatter = true;
+    options.constructs.gfm_strikethrough = true;
+    options.constructs.gfm_table = true;
+    options.constructs.gfm_task_list_item = true;
+    to_mdast(value, &options).ok()
+}
+
+pub fn tokenize(
+    input: Vec<char>,
+    tokens: &mut HighlighterTokenizer,
+    mdx: bool,
+    registry: &LanguageRegistry,
+) {
+    let value: String = input.iter().collect();
+    let mut painter = Painter::new(&input);
+    // MDX that does not parse, such as a snippet with an unclosed tag, is
+    // highlighted as Markdown
+    if let Some(tree) = parse(&value, mdx).or_else(|| parse(&value, false)) {
+        painter.visit(&tree);
+    }
+
+    let mut index = 0;
+    while index < input.len() {
+        let paint = painter.paint[index];
+        let mut end = index + 1;
+        while end < input.len() && painter.paint[end] == paint {
+            end += 1;
+        }
+        let value = input[index..end].to_vec();
+        match paint {
+            Paint::Language(language) => registry.lex(value, &painter.languages[language], tokens),
+            Paint::Text => {
+                for ch in value {
+                    let token = if ch == '\n' {
+                        Token::ENDL(ch)
+                    } else {
+                        Token::CH(ch)
+                    };
+                    lexers::add_token(tokens, token);
+                }
+            }
+            Paint::Heading => lexers::add_token(tokens, Token::HEADING(value)),
+            Paint::Emphasis => lexers::add_token(tokens, Token::EMPHASIS(value)),
+            Paint::Strong => lexers::add_token(tokens, Token::STRONG(value)),
+            Paint::Delete => lexers::add_token(tokens, Token::DELETED(value)),
+            Paint::InlineCode => lexers::add_token(tokens, Token::CONSTANT(value)),
+            Paint::Link => lexers::add_token(tokens, Token::STRING(value)),
+            Paint::Marker => lexers::add_token(tokens, Token::PUNCTUATION(value)),
+            Paint::Info => lexers::add_token(tokens, Token::ATTRIBUTE(value)),
+        }
+        index = end;
+    }
+}
+
+pub struct MarkdownLexer {
+    /// Whether to lex MDX (ESM, expressions and JSX) rather than Markdown.
+    pub mdx: bool,
+}
+
+impl lexer::Lexer for MarkdownLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.mdx, &LanguageRegistry::default());
+    }
+
+    fn lex_with(
+        &self,
+        input: Vec<char>,
+        tokens: &mut HighlighterTokenizer,
+        registry: &LanguageRegistry,
+    ) {
+        tokenize(input, tokens, self.mdx, registry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, to_code, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn markdown() {
+        let code = "---\ntitle: a\n---\n\n# Hello *world*\n\nSee [the `docs`](https://a.b) and **b** ~~c~~.\n\n> d\n> e\n\n- [x] f\n1. g\n\n```js {1}\nconst h = 'i'\n```\n\n<div>j</div>\n";
+        let rows = highlight(code, "md");
+        assert_eq!(
+            with_class(&rows, "hl-h"),
+            vec![" Hello "],
+            "should highlight headings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-em"),
+            vec!["*world*"],
+            "should highlight emphasis"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-st"),
+            vec!["**b**"],
+            "should highlight strong"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-del"),
+            vec!["~~c~~"],
+            "should highlight strikethrough"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["a", "[the ", "](https://a.b)", "'i'"],
+            "should highlight links"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["`docs`"],
+            "should highlight inline code"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["---", ":", "---", "#", ">", ">", "- [x]", "1.", "```", "```"],
+            "should highlight markers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["title", "js {1}"],
+            "should highlight frontmatter and the info of fenced code"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["const"],
+            "should lex fenced code with the lexer of its language"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["div", "div"],
+            "should lex HTML"
+        );
+        assert_eq!(to_code(&rows), code, "should keep the code");
+    }
+
+    #[test]
+    fn mdx() {
+        let code = "import {Note} from './note.js'\n\n# Hi {name}\n\n<Note type=\"info\">\n  Some *text*\n</Note>\n";
+        let rows = highlight(code, "mdx");
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["import"],
+            "should lex ESM as JavaScript"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["#", "{", "}"],
+            "should highlight the braces of expressions"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["Note", "Note"],
+            "should lex JSX tags as JSX"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-em"),
+            vec!["*text*"],
+            "should highlight children of JSX"
+        );
+        assert_eq!(to_code(&rows), code, "should keep the code");
+
+        let rows = highlight("<Note>\n\n# a", "mdx");
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec!["#"],
+            "should highlight MDX that does not parse as Markdown"
+        );
+    }
+}
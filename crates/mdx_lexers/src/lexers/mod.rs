@@ -1,2 +1,70 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, token::Token};
+
+pub mod c;
+pub mod c_like;
+pub mod cpp;
+pub mod csharp;
+pub mod css;
+pub mod diff;
+pub mod dockerfile;
+pub mod go;
+pub mod graphql;
+pub mod html;
+pub mod ini;
+pub mod java;
 pub mod javascript;
+pub mod json;
+pub mod kotlin;
+pub mod makefile;
+pub mod markdown;
+pub mod python;
 pub mod raw;
+pub mod rust;
+pub mod shell;
+pub mod sql;
+pub mod swift;
+pub mod toml;
+pub mod typescript;
+pub mod yaml;
+
+/// Push a token with a value into `tokens`, splitting it in rows at line
+/// endings.
+///
+/// Line endings (`Token::ENDL`) close the current row, tokens without a kind
+/// are added as text.
+pub fn add_token(tokens: &mut HighlighterTokenizer, token: Token) {
+    let kind = token.kind();
+    match token {
+        Token::ENDL(_) => tokens.end_of_line(),
+        Token::CONSTANT(value)
+        | Token::INT(value)
+        | Token::STRING(value)
+        | Token::KEYWORD(value)
+        | Token::COMMENT(value)
+        | Token::ENTITY(value)
+        | Token::ENTITYTAG(value)
+        | Token::VAR(value)
+        | Token::TYPE(value)
+        | Token::META(value)
+        | Token::ATTRIBUTE(value)
+        | Token::PUNCTUATION(value)
+        | Token::ERROR(value)
+        | Token::PROMPT(value)
+        | Token::OUTPUT(value)
+        | Token::INSERTED(value)
+        | Token::DELETED(value)
+        | Token::HEADING(value)
+        | Token::EMPHASIS(value)
+        | Token::STRONG(value)
+        | Token::IDENT(value)
+        | Token::HEAD(value) => {
+            let value = value.iter().collect::<String>();
+            match kind {
+                Some(kind) => tokens.add_multiline_token(value, kind),
+                None => tokens.add_text(value),
+            }
+        }
+        Token::CH(ch) => tokens.add_text(ch.to_string()),
+        Token::ILLEGAL | Token::EOF => {}
+    }
+}
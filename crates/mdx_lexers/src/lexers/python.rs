@@ -0,0 +1,535 @@
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token, token_kind::TokenKind,
+};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+}
+
+fn is_letter(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    is_letter(ch) || ch.is_numeric()
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    fn read_identifier(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_identifier_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Length of the prefix (`r`, `b`, `f`, `rb`, ...) of the string starting
+    /// at the current position, and whether it is an f-string.
+    fn string_start(&self) -> Option<(usize, bool)> {
+        if self.position > 0
+            && matches!(self.input.get(self.position - 1), Some(ch) if is_identifier_char(*ch))
+        {
+            return None;
+        }
+        let mut prefix = String::new();
+        while prefix.len() < 3 && is_letter(self.peek_char(prefix.len())) {
+            prefix.push(self.peek_char(prefix.len()).to_ascii_lowercase());
+        }
+        while !prefix.is_empty() {
+            if matches!(self.peek_char(prefix.len()), '"' | '\'')
+                && matches!(
+                    &prefix[..],
+                    "r" | "u" | "b" | "f" | "rb" | "br" | "fr" | "rf"
+                )
+            {
+                return Some((prefix.len(), prefix.contains('f')));
+            }
+            prefix.pop();
+        }
+        if matches!(self.ch, '"' | '\'') {
+            Some((0, false))
+        } else {
+            None
+        }
+    }
+
+    /// Read the quote of a string (`"`, `'''`, ...) at the current position.
+    fn read_quote(&mut self) -> Vec<char> {
+        let quote = self.ch;
+        if self.peek_char(1) == quote && self.peek_char(2) == quote {
+            self.read_char();
+            self.read_char();
+            self.read_char();
+            vec![quote; 3]
+        } else {
+            self.read_char();
+            vec![quote]
+        }
+    }
+
+    /// Whether the closing `quote` of a string is at the current position.
+    fn is_closing_quote(&self, quote: &[char]) -> bool {
+        (0..quote.len()).all(|offset| self.peek_char(offset) == quote[0])
+    }
+
+    /// Read a string, from the current position at its prefix.
+    ///
+    /// Strings that are not triple-quoted end at the end of the line when
+    /// they are not closed.
+    fn read_string(&mut self, prefix: usize) -> Vec<char> {
+        let position = self.position;
+        for _ in 0..prefix {
+            self.read_char();
+        }
+        let quote = self.read_quote();
+        while self.position < self.input.len() {
+            if self.is_closing_quote(&quote) {
+                for _ in 0..quote.len() {
+                    self.read_char();
+                }
+                break;
+            }
+            if self.ch == '\n' && quote.len() == 1 {
+                break;
+            }
+            if self.ch == '\\' && self.read_position < self.input.len() {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        if self.ch == '0' && matches!(self.peek_char(1), 'x' | 'X' | 'o' | 'O' | 'b' | 'B') {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len()
+                && (self.ch.is_ascii_hexdigit() || self.ch == '_')
+            {
+                self.read_char();
+            }
+            return self.input[position..self.position].to_vec();
+        }
+        while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+            self.read_char();
+        }
+        if self.ch == '.' {
+            self.read_char();
+            while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+                self.read_char();
+            }
+        }
+        if matches!(self.ch, 'e' | 'E')
+            && (self.peek_char(1).is_numeric()
+                || (matches!(self.peek_char(1), '+' | '-') && self.peek_char(2).is_numeric()))
+        {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+                self.read_char();
+            }
+        }
+        // Imaginary numbers (`1j`)
+        if matches!(self.ch, 'j' | 'J') {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether only whitespace is before `position` on its line.
+    fn is_line_start(&self, position: usize) -> bool {
+        self.input[..position]
+            .iter()
+            .rev()
+            .take_while(|ch| **ch != '\n')
+            .all(|ch| ch.is_whitespace())
+    }
+
+    /// Whether the line of `position` ends with `:`, ignoring comments.
+    fn is_block_line(&self, position: usize) -> bool {
+        let line: String = self.input[position..]
+            .iter()
+            .take_while(|ch| **ch != '\n')
+            .collect();
+        let line = line.split('#').next().unwrap_or_default();
+        line.trim_end().ends_with(':')
+    }
+
+    /// Next character after `position` that is not whitespace.
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| **ch != ' ' && **ch != '\t')
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Last word before `position`.
+    fn previous_word(&self, position: usize) -> String {
+        let mut end = position;
+        while end > 0 && (self.input[end - 1] == ' ' || self.input[end - 1] == '\t') {
+            end -= 1;
+        }
+        let mut start = end;
+        while start > 0 && is_identifier_char(self.input[start - 1]) {
+            start -= 1;
+        }
+        self.input[start..end].iter().collect()
+    }
+
+    /// Position of the `}` closing the expression of an f-string that starts
+    /// at `position`.
+    fn find_closing_brace(&self, position: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut index = position;
+        while index < self.input.len() {
+            let ch = self.input[index];
+            if let Some(quote_ch) = quote {
+                if ch == '\\' {
+                    index += 1;
+                } else if ch == quote_ch {
+                    quote = None;
+                }
+            } else {
+                match ch {
+                    '"' | '\'' => quote = Some(ch),
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' => depth -= 1,
+                    '}' if depth == 0 => return Some(index),
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+            }
+            index += 1;
+        }
+        None
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if let Some((prefix, _)) = self.string_start() {
+            return Token::STRING(self.read_string(prefix));
+        }
+        if self.ch == '@' && self.is_line_start(position) && is_letter(self.peek_char(1)) {
+            self.read_char();
+            while self.position < self.input.len()
+                && (is_identifier_char(self.ch) || self.ch == '.')
+            {
+                self.read_char();
+            }
+            return Token::META(self.input[position..self.position].to_vec());
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            '\0' if self.position >= self.input.len() => Token::EOF,
+            '#' => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::COMMENT(self.input[position..self.position].to_vec())
+            }
+            '.' if self.peek_char(1).is_numeric()
+                && (position == 0 || !is_identifier_char(self.input[position - 1])) =>
+            {
+                Token::INT(self.read_number())
+            }
+            ch if ch.is_numeric() => Token::INT(self.read_number()),
+            ch if is_letter(ch) => {
+                let identifier = self.read_identifier();
+                let id: String = identifier.iter().collect();
+
+                // Soft keywords are only keywords at the start of a block
+                if matches!(&id[..], "match" | "case")
+                    && self.is_line_start(position)
+                    && !matches!(self.next_char(self.position), '=' | '.' | ',' | ')')
+                    && self.is_block_line(position)
+                {
+                    return Token::KEYWORD(identifier);
+                }
+                if let Ok(token) = get_keyword_token(&identifier) {
+                    return token;
+                }
+                if matches!(&self.previous_word(position)[..], "def" | "class")
+                    || self.next_char(self.position) == '('
+                {
+                    return Token::ENTITY(identifier);
+                }
+                if id.len() > 1
+                    && id.starts_with(char::is_uppercase)
+                    && id
+                        .chars()
+                        .all(|ch| ch.is_uppercase() || ch.is_numeric() || ch == '_')
+                {
+                    return Token::CONSTANT(identifier);
+                }
+                Token::IDENT(identifier)
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+pub fn get_keyword_token(identifier: &[char]) -> Result<Token, String> {
+    let id: String = identifier.iter().collect();
+    match &id[..] {
+        "True" | "False" | "None" => Ok(Token::CONSTANT(identifier.to_vec())),
+        "self" | "cls" => Ok(Token::VAR(identifier.to_vec())),
+        "and" | "as" | "assert" | "async" | "await" | "break" | "class" | "continue" | "def"
+        | "del" | "elif" | "else" | "except" | "finally" | "for" | "from" | "global" | "if"
+        | "import" | "in" | "is" | "lambda" | "nonlocal" | "not" | "or" | "pass" | "raise"
+        | "return" | "try" | "while" | "with" | "yield" => Ok(Token::KEYWORD(identifier.to_vec())),
+        _ => Err(String::from("Not a keyword")),
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    tokenize_lexer(&mut l, tokens);
+}
+
+fn tokenize_lexer(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    loop {
+        if let Some((prefix, true)) = l.string_start() {
+            read_fstring(l, prefix, tokens);
+            continue;
+        }
+
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+/// Read an f-string, lexing the expressions between its braces as Python.
+fn read_fstring(l: &mut Lexer, prefix: usize, tokens: &mut HighlighterTokenizer) {
+    let mut position = l.position;
+    for _ in 0..prefix {
+        l.read_char();
+    }
+    let quote = l.read_quote();
+
+    let add_string = |tokens: &mut HighlighterTokenizer, value: &[char]| {
+        if !value.is_empty() {
+            tokens.add_multiline_token(value.iter().collect(), TokenKind::String);
+        }
+    };
+
+    while l.position < l.input.len() {
+        if l.is_closing_quote(&quote) {
+            for _ in 0..quote.len() {
+                l.read_char();
+            }
+            break;
+        }
+        if l.ch == '\n' && quote.len() == 1 {
+            break;
+        }
+        if l.ch == '{' && l.peek_char(1) == '{' {
+            l.read_char();
+        } else if l.ch == '{' {
+            add_string(tokens, &l.input[position..l.position]);
+            read_fstring_expression(l, tokens);
+            position = l.position;
+            continue;
+        } else if l.ch == '\\' && l.read_position < l.input.len() {
+            l.read_char();
+        }
+        l.read_char();
+    }
+
+    add_string(tokens, &l.input[position..l.position.min(l.input.len())]);
+}
+
+/// Read a replacement field (`{value!r:>10}`) of an f-string, lexing the
+/// expression as Python and the conversion and format spec as string.
+fn read_fstring_expression(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let start_position = l.position + 1;
+    let end_position = l
+        .find_closing_brace(start_position)
+        .unwrap_or(l.input.len());
+    tokens.add_text("{".into());
+
+    // Conversion (`!r`) or format spec (`:>10`), outside brackets
+    let mut depth = 0;
+    let mut expression_end = end_position;
+    for index in start_position..end_position {
+        match l.input[index] {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '!' if depth == 0 && l.input.get(index + 1) != Some(&'=') => {
+                expression_end = index;
+                break;
+            }
+            ':' if depth == 0 => {
+                expression_end = index;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let mut expression = Lexer::new(l.input[start_position..expression_end].to_vec());
+    expression.read_char();
+    tokenize_lexer(&mut expression, tokens);
+    if expression_end < end_position {
+        tokens.add_multiline_token(
+            l.input[expression_end..end_position].iter().collect(),
+            TokenKind::String,
+        );
+    }
+
+    l.read_position = end_position;
+    l.read_char();
+    if l.ch == '}' {
+        tokens.add_text("}".into());
+        l.read_char();
+    }
+}
+
+pub struct PythonLexer;
+
+impl lexer::Lexer for PythonLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::with_class, LanguageRegistry};
+    use pretty_assertions::assert_eq;
+
+    fn highlight(code: &str) -> Vec<mdxjs::hast::Node> {
+        LanguageRegistry::default().highlight(code.chars().collect(), "python")
+    }
+
+    #[test]
+    fn python() {
+        let rows = highlight(
+            "@app.route(\"/\")\nclass Foo(Base):\n    def greet(self, name=None):\n        # Say hi\n        return print(name or DEFAULT_NAME, a @ b)",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["@app.route"],
+            "should highlight decorators"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["class", "def", "return", "or"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["Foo", "greet", "print"],
+            "should highlight definitions and calls"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["None", "DEFAULT_NAME"],
+            "should highlight constants"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# Say hi"],
+            "should highlight comments"
+        );
+    }
+
+    #[test]
+    fn soft_keywords() {
+        let rows = highlight("match command:\n    case [x, *_]:\n        match = case\n");
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["match", "case"],
+            "should only highlight soft keywords that start a block"
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let rows = highlight("a = '''one\ntwo''' + b\"x\\\"\" + rb'y'\n\"unterminated\nc = 1");
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["'''one", "two'''", "b\"x\\\"\"", "rb'y'", "\"unterminated"],
+            "should highlight strings, splitting triple-quoted strings in rows"
+        );
+        assert_eq!(
+            rows.len(),
+            4,
+            "should end unterminated strings at the end of a line"
+        );
+
+        let rows = highlight("f\"{{a}} {user.name!r:>{width}} {len(x) + 1}\"");
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["f\"{{a}} ", "!r:>{width}", " ", "\""],
+            "should highlight f-strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["len"],
+            "should lex f-string expressions as Python"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1"],
+            "should lex f-string expressions"
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        let rows = highlight("x = [1_000, 0xFF, 0o17, 0b1010, 3.14, .5, 1e-3, 2j, 1.5J]");
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1_000", "0xFF", "0o17", "0b1010", "3.14", ".5", "1e-3", "2j", "1.5J"],
+            "should highlight numbers"
+        );
+    }
+}
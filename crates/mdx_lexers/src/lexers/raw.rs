@@ -1,6 +1,4 @@
-use mdxjs::hast;
-
-use crate::{highlighter_tokenizer::HighlighterTokenizer, token::Token};
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, token::Token};
 
 pub struct Lexer {
     input: Vec<char>,
@@ -88,14 +86,12 @@ pub fn get_keyword_token(identifier: &Vec<char>) -> Result<Token, String> {
     }
 }
 
-pub fn highlight(input: Vec<char>) -> Vec<hast::Node> {
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
     let mut l = Lexer::new(input);
     l.read_char();
-    let mut tokens = HighlighterTokenizer::new();
     loop {
         let token = l.next_token();
         if token == Token::EOF {
-            tokens.end_of_line();
             break;
         }
 
@@ -107,21 +103,17 @@ pub fn highlight(input: Vec<char>) -> Vec<hast::Node> {
                 tokens.end_of_line();
             }
             _ => {
-                if l.ch == '<' {
-                    tokens.add_text("&lt;".into());
-                    l.read_char();
-                    continue;
-                }
-                if l.ch == '>' {
-                    tokens.add_text("&gt;".into());
-                    l.read_char();
-                    continue;
-                }
                 tokens.add_text(l.ch.to_string());
                 l.read_char();
             }
         }
     }
+}
+
+pub struct RawLexer;
 
-    tokens.get_highlighted_code()
+impl lexer::Lexer for RawLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
 }
@@ -0,0 +1,486 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+}
+
+fn is_letter(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    is_letter(ch) || ch.is_numeric()
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    fn read_identifier(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_identifier_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Read a string up to its closing `"`, from the current position.
+    fn read_string(&mut self, position: usize) -> Vec<char> {
+        self.read_char();
+        while self.position < self.input.len() && self.ch != '"' {
+            if self.ch == '\\' {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.read_char();
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read a raw string (`r#"a"#`), from the current position at `r#` or
+    /// `r"`.
+    fn read_raw_string(&mut self, position: usize) -> Vec<char> {
+        self.read_char();
+        let mut hashes = 0;
+        while self.ch == '#' {
+            hashes += 1;
+            self.read_char();
+        }
+        self.read_char();
+        while self.position < self.input.len() {
+            if self.ch == '"' && (1..=hashes).all(|offset| self.peek_char(offset) == '#') {
+                for _ in 0..=hashes {
+                    self.read_char();
+                }
+                break;
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Whether a raw string starts at the current position (`r"`, `r#"`).
+    fn is_raw_string_start(&self) -> bool {
+        if self.ch != 'r' {
+            return false;
+        }
+        let mut offset = 1;
+        while self.peek_char(offset) == '#' {
+            offset += 1;
+        }
+        self.peek_char(offset) == '"'
+    }
+
+    /// Read a char literal (`'a'`, `'\n'`, `'\u{1F600}'`) or a lifetime
+    /// (`'a`), from the current position at `'`.
+    fn read_quote(&mut self, position: usize) -> Token {
+        let char_end = if self.peek_char(1) == '\\' {
+            // The escaped character is skipped, so that `'\''` is closed
+            match self.peek_char(2) {
+                'u' if self.peek_char(3) == '{' => {
+                    let mut offset = 4;
+                    while !matches!(self.peek_char(offset), '}' | '\'' | '\n' | '\0') {
+                        offset += 1;
+                    }
+                    Some(offset + 1)
+                }
+                'x' => Some(5),
+                _ => Some(3),
+            }
+        } else if self.peek_char(2) == '\'' {
+            Some(2)
+        } else {
+            None
+        };
+
+        if let Some(end) = char_end.filter(|end| self.peek_char(*end) == '\'') {
+            for _ in 0..=end {
+                self.read_char();
+            }
+            return Token::STRING(self.input[position..self.position].to_vec());
+        }
+
+        self.read_char();
+        if is_letter(self.ch) {
+            self.read_identifier();
+            return Token::VAR(self.input[position..self.position].to_vec());
+        }
+        // Byte literals that are not closed keep their prefix (`b'`)
+        if self.position - position > 1 {
+            return Token::STRING(self.input[position..self.position].to_vec());
+        }
+        Token::CH('\'')
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        if self.ch == '0' && matches!(self.peek_char(1), 'x' | 'o' | 'b') {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len()
+                && (self.ch.is_ascii_hexdigit() || self.ch == '_')
+            {
+                self.read_char();
+            }
+        } else {
+            while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+                self.read_char();
+            }
+            if self.ch == '.' && self.peek_char(1).is_numeric() {
+                self.read_char();
+                while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+                    self.read_char();
+                }
+            }
+            if matches!(self.ch, 'e' | 'E')
+                && (self.peek_char(1).is_numeric()
+                    || (matches!(self.peek_char(1), '+' | '-') && self.peek_char(2).is_numeric()))
+            {
+                self.read_char();
+                self.read_char();
+                while self.position < self.input.len() && (self.ch.is_numeric() || self.ch == '_') {
+                    self.read_char();
+                }
+            }
+        }
+        // Suffix (`u8`, `f64`, `usize`)
+        if matches!(self.ch, 'u' | 'i' | 'f') {
+            self.read_identifier();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Read a block comment, which can be nested, from the current position
+    /// at `/*`.
+    fn read_block_comment(&mut self) -> Vec<char> {
+        let position = self.position;
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            if self.ch == '/' && self.peek_char(1) == '*' {
+                depth += 1;
+                self.read_char();
+            } else if self.ch == '*' && self.peek_char(1) == '/' {
+                depth -= 1;
+                self.read_char();
+                if depth == 0 {
+                    self.read_char();
+                    break;
+                }
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read an attribute (`#[derive(Debug)]`, `#![allow(a)]`) up to its
+    /// closing bracket, from the current position at `#`.
+    fn read_attribute(&mut self) -> Vec<char> {
+        let position = self.position;
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            match self.ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.read_char();
+                        break;
+                    }
+                }
+                '"' => {
+                    let start = self.position;
+                    self.read_string(start);
+                    continue;
+                }
+                _ => {}
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Next character after `position` that is not whitespace.
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Last word before `position`.
+    fn previous_word(&self, position: usize) -> String {
+        let mut end = position;
+        while end > 0 && self.input[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        let mut start = end;
+        while start > 0 && is_identifier_char(self.input[start - 1]) {
+            start -= 1;
+        }
+        self.input[start..end].iter().collect()
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.ch == '/' && self.peek_char(1) == '/' {
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            return Token::COMMENT(self.input[position..self.position].to_vec());
+        }
+        if self.ch == '/' && self.peek_char(1) == '*' {
+            return Token::COMMENT(self.read_block_comment());
+        }
+        if self.ch == '#'
+            && (self.peek_char(1) == '[' || (self.peek_char(1) == '!' && self.peek_char(2) == '['))
+        {
+            return Token::META(self.read_attribute());
+        }
+        if self.is_raw_string_start() {
+            return Token::STRING(self.read_raw_string(position));
+        }
+        if self.ch == 'b' && self.peek_char(1) == '"' {
+            self.read_char();
+            return Token::STRING(self.read_string(position));
+        }
+        if self.ch == 'b' && self.peek_char(1) == 'r' && matches!(self.peek_char(2), '"' | '#') {
+            self.read_char();
+            return Token::STRING(self.read_raw_string(position));
+        }
+        if self.ch == 'b' && self.peek_char(1) == '\'' {
+            self.read_char();
+            return self.read_quote(position);
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            '\0' if self.position >= self.input.len() => Token::EOF,
+            '"' => Token::STRING(self.read_string(position)),
+            '\'' => self.read_quote(position),
+            ch if ch.is_numeric() => Token::INT(self.read_number()),
+            ch if is_letter(ch) => {
+                let identifier = self.read_identifier();
+                if let Ok(token) = get_keyword_token(&identifier) {
+                    return token;
+                }
+                let id: String = identifier.iter().collect();
+
+                // Macros (`println!`), but not `!=`
+                if self.ch == '!' && self.peek_char(1) != '=' {
+                    self.read_char();
+                    return Token::ENTITY(self.input[position..self.position].to_vec());
+                }
+                if is_primitive_type(&id) || id.starts_with(char::is_uppercase) {
+                    if id.len() > 1
+                        && id
+                            .chars()
+                            .all(|ch| ch.is_uppercase() || ch.is_numeric() || ch == '_')
+                    {
+                        return Token::CONSTANT(identifier);
+                    }
+                    return Token::TYPE(identifier);
+                }
+                if self.previous_word(position) == "fn"
+                    || self.next_char(self.position) == '('
+                    || (self.ch == ':' && self.peek_char(1) == ':' && self.peek_char(2) == '<')
+                {
+                    return Token::ENTITY(identifier);
+                }
+                if position > 0
+                    && self.input[position - 1] == '.'
+                    && !self.peek_char(0).is_numeric()
+                {
+                    return Token::ENTITY(identifier);
+                }
+                Token::IDENT(identifier)
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+fn is_primitive_type(id: &str) -> bool {
+    matches!(
+        id,
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "char"
+            | "str"
+    )
+}
+
+pub fn get_keyword_token(identifier: &[char]) -> Result<Token, String> {
+    let id: String = identifier.iter().collect();
+    match &id[..] {
+        "true" | "false" => Ok(Token::CONSTANT(identifier.to_vec())),
+        "self" | "Self" | "super" | "crate" => Ok(Token::VAR(identifier.to_vec())),
+        "as" | "async" | "await" | "break" | "const" | "continue" | "dyn" | "else" | "enum"
+        | "extern" | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod"
+        | "move" | "mut" | "pub" | "ref" | "return" | "static" | "struct" | "trait" | "type"
+        | "union" | "unsafe" | "use" | "where" | "while" | "yield" | "macro_rules" => {
+            Ok(Token::KEYWORD(identifier.to_vec()))
+        }
+        _ => Err(String::from("Not a keyword")),
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct RustLexer;
+
+impl lexer::Lexer for RustLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::with_class, LanguageRegistry};
+    use pretty_assertions::assert_eq;
+
+    fn highlight(code: &str) -> Vec<mdxjs::hast::Node> {
+        LanguageRegistry::default().highlight(code.chars().collect(), "rust")
+    }
+
+    #[test]
+    fn rust() {
+        let rows = highlight(
+            "#[derive(Debug)]\npub struct Foo<'a> {\n    name: &'a str,\n}\n\nimpl<'a> Foo<'a> {\n    fn new(name: &'a str) -> Self {\n        println!(\"{}\", name.len());\n        Self { name }\n    }\n}",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["#[derive(Debug)]"],
+            "should highlight attributes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["pub", "struct", "impl", "fn"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["'a", "'a", "'a", "'a", "'a", "Self", "Self"],
+            "should highlight lifetimes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["Foo", "str", "Foo", "str"],
+            "should highlight types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["new", "println!", "len"],
+            "should highlight functions and macros"
+        );
+    }
+
+    #[test]
+    fn literals() {
+        let rows =
+            highlight("let a = ('a', '\\n', b'x', \"b\\\"\", r#\"c\"d\"#, br\"e\", b\"f\");\nlet b = ['\\'', b'\\'', '\\x41', '\\u{1F600}'];");
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec![
+                "'a'",
+                "'\\n'",
+                "b'x'",
+                "\"b\\\"\"",
+                "r#\"c\"d\"#",
+                "br\"e\"",
+                "b\"f\"",
+                "'\\''",
+                "b'\\''",
+                "'\\x41'",
+                "'\\u{1F600}'"
+            ],
+            "should highlight strings, chars and bytes"
+        );
+
+        let rows = highlight(
+            "let a = [1_000u32, 0xFF_u8, 1.5e-3f64, 0b1010, 2.0];\nlet b = MAX_SIZE;\na.0",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec![
+                "1_000u32",
+                "0xFF_u8",
+                "1.5e-3f64",
+                "0b1010",
+                "2.0",
+                "MAX_SIZE",
+                "0"
+            ],
+            "should highlight numbers and constants"
+        );
+    }
+
+    #[test]
+    fn comments() {
+        let rows = highlight("/// Doc\n//! Inner\n/* a /* b */ c */ d\n/*\n*/");
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["/// Doc", "//! Inner", "/* a /* b */ c */", "/*", "*/"],
+            "should highlight line, doc and nested block comments"
+        );
+    }
+}
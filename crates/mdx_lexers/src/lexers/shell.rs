@@ -0,0 +1,676 @@
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token, token_kind::TokenKind,
+};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Delimiters of heredocs whose body starts on the next line, and whether
+    /// leading tabs are stripped (`<<-`).
+    heredocs: Vec<(String, bool)>,
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Whether `ch` ends a word.
+fn is_word_end(ch: char) -> bool {
+    ch.is_whitespace() || "'\"`$;|&()<>".contains(ch)
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            heredocs: vec![],
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Whether a word starts at `position`.
+    fn is_word_start(&self, position: usize) -> bool {
+        position == 0 || is_word_end(self.input[position - 1])
+    }
+
+    /// Whether the word at `position` is a command, not an argument.
+    fn is_command_position(&self, position: usize) -> bool {
+        let mut index = position;
+        while index > 0 && matches!(self.input[index - 1], ' ' | '\t') {
+            index -= 1;
+        }
+        if index == 0 || "\n;|&({`".contains(self.input[index - 1]) {
+            return true;
+        }
+        let mut start = index;
+        while start > 0 && is_name_char(self.input[start - 1]) {
+            start -= 1;
+        }
+        let word: String = self.input[start..index].iter().collect();
+        matches!(
+            &word[..],
+            "then" | "do" | "else" | "elif" | "if" | "while" | "until" | "sudo" | "time" | "exec"
+        )
+    }
+
+    fn read_word(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && !is_word_end(self.ch) {
+            // Line continuations
+            if self.ch == '\\' && self.peek_char(1) == '\n' {
+                break;
+            }
+            if self.ch == '\\' {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read a variable (`$a`, `${a:-b}`, `$1`, `$?`), from the current
+    /// position at `$`.
+    fn read_variable(&mut self) -> Vec<char> {
+        let position = self.position;
+        self.read_char();
+        if self.ch == '{' {
+            while self.position < self.input.len() && self.ch != '}' && self.ch != '\n' {
+                self.read_char();
+            }
+            if self.ch == '}' {
+                self.read_char();
+            }
+        } else if is_name_char(self.ch) && !self.ch.is_numeric() {
+            while self.position < self.input.len() && is_name_char(self.ch) {
+                self.read_char();
+            }
+        } else {
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Whether a variable starts at the current position.
+    fn is_variable_start(&self) -> bool {
+        let next = self.peek_char(1);
+        self.ch == '$' && (next == '{' || is_name_char(next) || "@*#?$!-".contains(next))
+    }
+
+    /// Position of the `)` closing the `(` at `position`, skipping quotes.
+    fn find_closing_paren(&self, position: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut index = position;
+        while index < self.input.len() {
+            let ch = self.input[index];
+            if let Some(quote_ch) = quote {
+                if ch == '\\' && quote_ch != '\'' {
+                    index += 1;
+                } else if ch == quote_ch {
+                    quote = None;
+                }
+            } else {
+                match ch {
+                    '\\' => index += 1,
+                    '"' | '\'' | '`' => quote = Some(ch),
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(index);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// Read the start of a heredoc (`<<EOF`, `<<-'EOF'`), from the current
+    /// position at `<<`.
+    fn read_heredoc_start(&mut self) -> Vec<char> {
+        let position = self.position;
+        self.read_char();
+        self.read_char();
+        let strip_tabs = self.ch == '-';
+        if strip_tabs {
+            self.read_char();
+        }
+        while self.ch == ' ' {
+            self.read_char();
+        }
+        let quote = if matches!(self.ch, '\'' | '"') {
+            Some(self.ch)
+        } else {
+            None
+        };
+        if quote.is_some() {
+            self.read_char();
+        }
+        let start = self.position;
+        while self.position < self.input.len() && is_name_char(self.ch) {
+            self.read_char();
+        }
+        self.heredocs.push((
+            self.input[start..self.position].iter().collect(),
+            strip_tabs,
+        ));
+        if quote.is_some() && Some(self.ch) == quote {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether a heredoc starts at the current position.
+    fn is_heredoc_start(&self) -> bool {
+        if self.ch != '<' || self.peek_char(1) != '<' || self.peek_char(2) == '<' {
+            return false;
+        }
+        let mut offset = 2;
+        while matches!(self.peek_char(offset), '-' | ' ' | '\'' | '"') {
+            offset += 1;
+        }
+        is_name_char(self.peek_char(offset))
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '#' if self.is_word_start(position) => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::COMMENT(self.input[position..self.position].to_vec())
+            }
+            '\'' => {
+                self.read_char();
+                while self.position < self.input.len() && self.ch != '\'' {
+                    self.read_char();
+                }
+                self.read_char();
+                Token::STRING(self.input[position..self.position.min(self.input.len())].to_vec())
+            }
+            // ANSI-C quoting (`$'a\n'`)
+            '$' if self.peek_char(1) == '\'' => {
+                self.read_char();
+                self.read_char();
+                while self.position < self.input.len() && self.ch != '\'' {
+                    if self.ch == '\\' {
+                        self.read_char();
+                    }
+                    self.read_char();
+                }
+                self.read_char();
+                Token::STRING(self.input[position..self.position.min(self.input.len())].to_vec())
+            }
+            '\\' if self.peek_char(1) == '\n' => {
+                self.read_char();
+                Token::CH('\\')
+            }
+            _ if self.is_variable_start() => Token::VAR(self.read_variable()),
+            _ if self.is_heredoc_start() => Token::META(self.read_heredoc_start()),
+            '-' if self.is_word_start(position)
+                && (self.peek_char(1).is_alphanumeric() || self.peek_char(1) == '-') =>
+            {
+                while self.position < self.input.len() && !is_word_end(self.ch) && self.ch != '=' {
+                    self.read_char();
+                }
+                Token::ATTRIBUTE(self.input[position..self.position].to_vec())
+            }
+            ch if !is_word_end(ch) => {
+                let is_word_start = self.is_word_start(position);
+                let is_command = is_word_start && self.is_command_position(position);
+                let word = self.read_word();
+                let name: String = word.iter().collect();
+
+                if !is_word_start {
+                    return Token::IDENT(word);
+                }
+                // Assignments (`a=1`)
+                if let Some(index) = name.find('=') {
+                    if index > 0 && name[..index].chars().all(is_name_char) {
+                        let value_position = position + name[..index].chars().count();
+                        self.read_position = value_position;
+                        self.read_char();
+                        return Token::VAR(word[..value_position - position].to_vec());
+                    }
+                }
+                if name.chars().all(|ch| ch.is_ascii_digit()) {
+                    return Token::INT(word);
+                }
+                if let Ok(token) = get_keyword_token(&word) {
+                    if is_command || !matches!(token, Token::KEYWORD(_)) {
+                        return token;
+                    }
+                }
+                if is_command && is_builtin(&name) {
+                    return Token::ENTITY(word);
+                }
+                Token::IDENT(word)
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "alias"
+            | "bg"
+            | "bind"
+            | "builtin"
+            | "cd"
+            | "command"
+            | "declare"
+            | "echo"
+            | "eval"
+            | "exec"
+            | "exit"
+            | "export"
+            | "fg"
+            | "getopts"
+            | "hash"
+            | "jobs"
+            | "kill"
+            | "let"
+            | "local"
+            | "printf"
+            | "pwd"
+            | "read"
+            | "readonly"
+            | "set"
+            | "shift"
+            | "source"
+            | "test"
+            | "trap"
+            | "type"
+            | "ulimit"
+            | "umask"
+            | "unalias"
+            | "unset"
+            | "wait"
+    )
+}
+
+pub fn get_keyword_token(identifier: &[char]) -> Result<Token, String> {
+    let id: String = identifier.iter().collect();
+    match &id[..] {
+        "true" | "false" => Ok(Token::CONSTANT(identifier.to_vec())),
+        "if" | "then" | "else" | "elif" | "fi" | "for" | "in" | "while" | "until" | "do"
+        | "done" | "case" | "esac" | "function" | "select" | "return" | "break" | "continue"
+        | "time" => Ok(Token::KEYWORD(identifier.to_vec())),
+        _ => Err(String::from("Not a keyword")),
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    tokenize_lexer(&mut l, tokens);
+}
+
+fn tokenize_lexer(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    loop {
+        match l.ch {
+            '"' => {
+                read_double_quoted(l, tokens);
+                continue;
+            }
+            '$' if l.peek_char(1) == '(' => {
+                read_substitution(l, tokens);
+                continue;
+            }
+            '`' => {
+                read_backticks(l, tokens);
+                continue;
+            }
+            _ => {}
+        }
+
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        let end_of_line = token == Token::ENDL('\n');
+        lexers::add_token(tokens, token);
+        if end_of_line && !l.heredocs.is_empty() {
+            read_heredoc_bodies(l, tokens);
+        }
+    }
+}
+
+/// Read a double-quoted string, lexing variables and command substitutions
+/// in it.
+fn read_double_quoted(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let mut position = l.position;
+    l.read_char();
+
+    let add_string = |tokens: &mut HighlighterTokenizer, value: &[char]| {
+        if !value.is_empty() {
+            tokens.add_multiline_token(value.iter().collect(), TokenKind::String);
+        }
+    };
+
+    while l.position < l.input.len() {
+        if l.ch == '"' {
+            l.read_char();
+            break;
+        }
+        if l.ch == '$' && l.peek_char(1) == '(' {
+            add_string(tokens, &l.input[position..l.position]);
+            read_substitution(l, tokens);
+            position = l.position;
+            continue;
+        }
+        if l.ch == '`' {
+            add_string(tokens, &l.input[position..l.position]);
+            read_backticks(l, tokens);
+            position = l.position;
+            continue;
+        }
+        if l.is_variable_start() {
+            add_string(tokens, &l.input[position..l.position]);
+            let variable = l.read_variable();
+            tokens.add_token(variable.iter().collect(), TokenKind::Var);
+            position = l.position;
+            continue;
+        }
+        if l.ch == '\\' {
+            l.read_char();
+        }
+        l.read_char();
+    }
+
+    add_string(tokens, &l.input[position..l.position.min(l.input.len())]);
+}
+
+/// Read a command substitution (`$(a)`, `$((1 + 2))`) and lex its commands.
+fn read_substitution(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let start_position = l.position + 2;
+    let end_position = l
+        .find_closing_paren(l.position + 1)
+        .unwrap_or(l.input.len());
+    tokens.add_text("$(".into());
+
+    let mut commands = Lexer::new(l.input[start_position..end_position].to_vec());
+    commands.read_char();
+    tokenize_lexer(&mut commands, tokens);
+
+    l.read_position = end_position;
+    l.read_char();
+    if l.ch == ')' {
+        tokens.add_text(")".into());
+        l.read_char();
+    }
+}
+
+/// Read a command substitution with backticks and lex its commands.
+fn read_backticks(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    let start_position = l.position + 1;
+    let mut end_position = start_position;
+    while end_position < l.input.len() && l.input[end_position] != '`' {
+        if l.input[end_position] == '\\' {
+            end_position += 1;
+        }
+        end_position += 1;
+    }
+    let end_position = end_position.min(l.input.len());
+    tokens.add_text("`".into());
+
+    let mut commands = Lexer::new(l.input[start_position..end_position].to_vec());
+    commands.read_char();
+    tokenize_lexer(&mut commands, tokens);
+
+    l.read_position = end_position;
+    l.read_char();
+    if l.ch == '`' {
+        tokens.add_text("`".into());
+        l.read_char();
+    }
+}
+
+/// Read the bodies of the heredocs started on the previous line, each up to
+/// and including the line with its delimiter.
+fn read_heredoc_bodies(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    for (delimiter, strip_tabs) in std::mem::take(&mut l.heredocs) {
+        while l.position < l.input.len() {
+            let start = l.position;
+            while l.position < l.input.len() && l.ch != '\n' {
+                l.read_char();
+            }
+            let line: String = l.input[start..l.position].iter().collect();
+            let line_delimiter = if strip_tabs {
+                line.trim_start_matches('\t')
+            } else {
+                &line
+            };
+            if line_delimiter == delimiter {
+                tokens.add_token(line, TokenKind::Meta);
+                break;
+            }
+            if !line.is_empty() {
+                tokens.add_token(line, TokenKind::String);
+            }
+            if l.ch == '\n' {
+                tokens.end_of_line();
+                l.read_char();
+            }
+        }
+    }
+}
+
+/// Length of the prompt (`$ `, `# `, `% `) at the start of `line`, if any.
+fn prompt_length(line: &[char]) -> Option<usize> {
+    let indent = line.iter().take_while(|ch| **ch == ' ').count();
+    match line.get(indent..indent + 2) {
+        Some(['$' | '#' | '%' | '>', ' ']) => Some(indent + 2),
+        _ if line.len() == indent + 1 && matches!(line[indent], '$' | '#' | '%') => {
+            Some(indent + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Lex a console session: lines starting with a prompt are commands, other
+/// lines are output.
+pub fn tokenize_console(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let lines: Vec<&[char]> = input.split(|ch| *ch == '\n').collect();
+    let mut after_command = false;
+    let mut index = 0;
+    while index < lines.len() {
+        if index > 0 {
+            tokens.end_of_line();
+        }
+        let line = lines[index];
+        let is_continuation = line.iter().find(|ch| **ch != ' ') == Some(&'>');
+        match prompt_length(line) {
+            // `>` only continues commands
+            Some(length) if !is_continuation || after_command => {
+                after_command = true;
+                lexers::add_token(tokens, Token::PROMPT(line[..length].to_vec()));
+
+                // Commands continued with `\` on the next lines
+                let mut command = line[length..].to_vec();
+                while command.last() == Some(&'\\') && index + 1 < lines.len() {
+                    index += 1;
+                    command.push('\n');
+                    command.extend_from_slice(lines[index]);
+                }
+                tokenize(command, tokens);
+            }
+            _ if !line.is_empty() => {
+                after_command = false;
+                lexers::add_token(tokens, Token::OUTPUT(line.to_vec()));
+            }
+            _ => after_command = false,
+        }
+        index += 1;
+    }
+}
+
+pub struct ShellLexer {
+    /// Whether the code is a console session, with prompts and output.
+    pub console: bool,
+}
+
+impl lexer::Lexer for ShellLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        if self.console {
+            tokenize_console(input, tokens);
+        } else {
+            tokenize(input, tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn shell() {
+        let rows = highlight(
+            "#!/bin/bash\n# Build\nexport NODE_ENV=production\nif [ -f \"$HOME/.rc\" ]; then\n  echo \"Hi ${USER:-you}, $(date +%Y)\" | grep -v --color=auto 'a#b' # done\nfi",
+            "bash",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["#!/bin/bash", "# Build", "# done"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["if", "then", "fi"],
+            "should highlight keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["export", "echo"],
+            "should highlight builtins"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["NODE_ENV", "$HOME", "${USER:-you}"],
+            "should highlight variables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["-f", "-v", "--color"],
+            "should highlight flags"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"", "/.rc\"", "\"Hi ", ", ", "\"", "'a#b'"],
+            "should highlight strings around variables and substitutions"
+        );
+    }
+
+    #[test]
+    fn heredoc() {
+        let rows = highlight("cat <<-EOF > a.txt\n\tHello $NAME\n\tEOF\necho done", "sh");
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["<<-EOF", "\tEOF"],
+            "should highlight heredoc delimiters"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\tHello $NAME"],
+            "should highlight heredocs"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["echo"],
+            "should lex after heredocs"
+        );
+        assert_eq!(rows.len(), 4, "should keep rows");
+    }
+
+    #[test]
+    fn console() {
+        let rows = highlight(
+            "$ npm install \\\n  --save mdx\nadded 1 package\n\n# whoami\nroot",
+            "console",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-pr"),
+            vec!["$ ", "# "],
+            "should highlight prompts"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-o"),
+            vec!["added 1 package", "root"],
+            "should highlight output"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["--save"],
+            "should lex continued commands"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            Vec::<String>::new(),
+            "should not see prompts as comments"
+        );
+        assert_eq!(rows.len(), 6, "should keep rows");
+    }
+
+    #[test]
+    fn continuation_prompts() {
+        let rows = highlight("> a\n$ for b in c\n> do echo $b; done\nc", "console");
+        assert_eq!(
+            with_class(&rows, "hl-pr"),
+            vec!["$ ", "> "],
+            "should highlight `>` after commands"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-o"),
+            vec!["> a", "c"],
+            "should not highlight `>` after output"
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+/// Flavour of SQL, which changes keywords, quotes and placeholders.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SqlDialect {
+    /// Standard SQL.
+    Standard,
+    /// PostgreSQL: dollar-quoted strings (`$$a$$`), `E'a\n'`, and `$1`.
+    Postgres,
+    /// MySQL and MariaDB: backtick identifiers, `"` strings, and `#`
+    /// comments.
+    Mysql,
+    /// SQLite: backtick and bracket identifiers, and `:a`, `@a`, `$a`.
+    Sqlite,
+}
+
+impl SqlDialect {
+    /// Dialect of a language name (`postgres`, `mysql`, `sqlite`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match &name.to_lowercase()[..] {
+            "sql" => Some(SqlDialect::Standard),
+            "postgres" | "postgresql" | "psql" | "pgsql" => Some(SqlDialect::Postgres),
+            "mysql" | "mariadb" => Some(SqlDialect::Mysql),
+            "sqlite" | "sqlite3" => Some(SqlDialect::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    pub dialect: SqlDialect,
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            dialect: SqlDialect::Standard,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Read a quoted string or identifier, where a doubled quote is an escaped
+    /// quote (`'it''s'`), and so is a backslash one if `backslash`.
+    fn read_quoted(&mut self, close: char, backslash: bool) -> Vec<char> {
+        let position = self.position;
+        self.read_char();
+        while self.position < self.input.len() {
+            if backslash && self.ch == '\\' {
+                self.read_char();
+            } else if self.ch == close {
+                if self.peek_char(1) != close || close == ']' {
+                    break;
+                }
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.read_char();
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Tag of a dollar-quoted string (`$$`, `$body$`) at the current
+    /// position.
+    fn dollar_quote_tag(&self) -> Option<Vec<char>> {
+        if self.ch != '$' {
+            return None;
+        }
+        let mut offset = 1;
+        while is_identifier_char(self.peek_char(offset)) && self.peek_char(offset) != '$' {
+            offset += 1;
+        }
+        if self.peek_char(offset) != '$' || self.peek_char(1).is_ascii_digit() {
+            return None;
+        }
+        Some(self.input[self.position..=self.position + offset].to_vec())
+    }
+
+    fn read_dollar_quoted(&mut self, tag: &[char]) -> Vec<char> {
+        let position = self.position;
+        let mut end = position + tag.len();
+        while end < self.input.len() && !self.input[end..].starts_with(tag) {
+            end += 1;
+        }
+        self.read_position = (end + tag.len()).min(self.input.len());
+        self.read_char();
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    fn read_number(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len()
+            && (self.ch.is_alphanumeric() || self.ch == '.' || self.ch == '_')
+        {
+            if matches!(self.ch, 'e' | 'E') && matches!(self.peek_char(1), '+' | '-') {
+                self.read_char();
+            }
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    fn read_identifier(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && is_identifier_char(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Next character after `position` that is not whitespace.
+    fn next_char(&self, position: usize) -> char {
+        self.input[position.min(self.input.len())..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        let line_comment = (self.ch == '-' && self.peek_char(1) == '-')
+            || (self.ch == '#' && self.dialect == SqlDialect::Mysql);
+        if line_comment {
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            return Token::COMMENT(self.input[position..self.position].to_vec());
+        }
+        if self.ch == '/' && self.peek_char(1) == '*' {
+            self.read_char();
+            self.read_char();
+            while self.position < self.input.len() && !(self.ch == '*' && self.peek_char(1) == '/')
+            {
+                self.read_char();
+            }
+            self.read_char();
+            self.read_char();
+            return Token::COMMENT(
+                self.input[position..self.position.min(self.input.len())].to_vec(),
+            );
+        }
+        if self.dialect == SqlDialect::Postgres {
+            if let Some(tag) = self.dollar_quote_tag() {
+                return Token::STRING(self.read_dollar_quoted(&tag));
+            }
+            // Escape strings (`E'a\n'`)
+            if matches!(self.ch, 'e' | 'E') && self.peek_char(1) == '\'' {
+                self.read_char();
+                let mut value = vec![self.input[position]];
+                value.append(&mut self.read_quoted('\'', true));
+                return Token::STRING(value);
+            }
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '\'' => Token::STRING(self.read_quoted('\'', self.dialect == SqlDialect::Mysql)),
+            '"' if self.dialect == SqlDialect::Mysql => Token::STRING(self.read_quoted('"', true)),
+            '"' => Token::ATTRIBUTE(self.read_quoted('"', false)),
+            '`' if matches!(self.dialect, SqlDialect::Mysql | SqlDialect::Sqlite) => {
+                Token::ATTRIBUTE(self.read_quoted('`', false))
+            }
+            '[' if self.dialect == SqlDialect::Sqlite => {
+                Token::ATTRIBUTE(self.read_quoted(']', false))
+            }
+            // Placeholders (`?`, `?1`, `$1`, `:a`, `@a`)
+            '?' => {
+                self.read_char();
+                while self.ch.is_ascii_digit() {
+                    self.read_char();
+                }
+                Token::VAR(self.input[position..self.position].to_vec())
+            }
+            '$' if self.peek_char(1).is_ascii_digit()
+                && matches!(self.dialect, SqlDialect::Postgres | SqlDialect::Standard) =>
+            {
+                self.read_char();
+                while self.ch.is_ascii_digit() {
+                    self.read_char();
+                }
+                Token::VAR(self.input[position..self.position].to_vec())
+            }
+            ':' if self.peek_char(1) == ':' => {
+                self.read_char();
+                self.read_char();
+                Token::IDENT(self.input[position..self.position].to_vec())
+            }
+            ':' | '@' | '$'
+                if (self.peek_char(1).is_alphabetic()
+                    || self.peek_char(1) == '_'
+                    || (self.ch == '@' && self.peek_char(1) == '@'))
+                    && (position == 0 || self.input[position - 1] != ':') =>
+            {
+                self.read_char();
+                if self.ch == '@' {
+                    self.read_char();
+                }
+                self.read_identifier();
+                Token::VAR(self.input[position..self.position].to_vec())
+            }
+            ch if ch.is_ascii_digit() || (ch == '.' && self.peek_char(1).is_ascii_digit()) => {
+                Token::INT(self.read_number())
+            }
+            ch if ch.is_alphabetic() || ch == '_' => {
+                let identifier = self.read_identifier();
+                // Types after casts (`a::int`)
+                if position >= 2 && self.input[position - 2..position] == [':', ':'] {
+                    return Token::TYPE(identifier);
+                }
+                match get_keyword_token(&identifier, self.dialect) {
+                    Ok(token) => token,
+                    Err(_) if self.next_char(self.position) == '(' => Token::ENTITY(identifier),
+                    Err(_) => Token::IDENT(identifier),
+                }
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+/// Token of a keyword, type or constant, whatever its case (`SELECT`,
+/// `select`).
+pub fn get_keyword_token(identifier: &[char], dialect: SqlDialect) -> Result<Token, String> {
+    let id: String = identifier.iter().collect::<String>().to_lowercase();
+    let is_dialect_keyword = match dialect {
+        SqlDialect::Standard => false,
+        SqlDialect::Postgres => matches!(
+            &id[..],
+            "analyze"
+                | "conflict"
+                | "declare"
+                | "do"
+                | "explain"
+                | "extension"
+                | "ilike"
+                | "language"
+                | "lateral"
+                | "listen"
+                | "materialized"
+                | "notify"
+                | "nothing"
+                | "owner"
+                | "perform"
+                | "raise"
+                | "returns"
+                | "sequence"
+                | "similar"
+                | "vacuum"
+        ),
+        SqlDialect::Mysql => matches!(
+            &id[..],
+            "auto_increment"
+                | "charset"
+                | "databases"
+                | "delimiter"
+                | "describe"
+                | "div"
+                | "duplicate"
+                | "engine"
+                | "ignore"
+                | "lock"
+                | "regexp"
+                | "rlike"
+                | "show"
+                | "tables"
+                | "unlock"
+                | "use"
+                | "xor"
+        ),
+        SqlDialect::Sqlite => matches!(
+            &id[..],
+            "abort"
+                | "attach"
+                | "autoincrement"
+                | "conflict"
+                | "detach"
+                | "fail"
+                | "glob"
+                | "ignore"
+                | "indexed"
+                | "pragma"
+                | "regexp"
+                | "rowid"
+                | "strict"
+                | "temp"
+                | "vacuum"
+                | "virtual"
+                | "without"
+        ),
+    };
+    if is_dialect_keyword {
+        return Ok(Token::KEYWORD(identifier.to_vec()));
+    }
+
+    match &id[..] {
+        "true" | "false" | "null" | "unknown" => Ok(Token::CONSTANT(identifier.to_vec())),
+        "add" | "all" | "alter" | "and" | "any" | "as" | "asc" | "begin" | "between" | "by"
+        | "cascade" | "case" | "check" | "column" | "commit" | "constraint" | "create"
+        | "cross" | "database" | "default" | "delete" | "desc" | "distinct" | "drop" | "else"
+        | "end" | "except" | "exists" | "fetch" | "first" | "for" | "foreign" | "from" | "full"
+        | "function" | "grant" | "group" | "having" | "if" | "in" | "index" | "inner"
+        | "insert" | "intersect" | "into" | "is" | "join" | "key" | "left" | "like" | "limit"
+        | "natural" | "next" | "not" | "of" | "offset" | "on" | "only" | "or" | "order"
+        | "outer" | "over" | "partition" | "primary" | "procedure" | "recursive" | "references"
+        | "replace" | "returning" | "revoke" | "right" | "rollback" | "row" | "rows" | "schema"
+        | "select" | "set" | "table" | "then" | "to" | "transaction" | "trigger" | "truncate"
+        | "union" | "unique" | "update" | "using" | "values" | "view" | "when" | "where"
+        | "window" | "with" => Ok(Token::KEYWORD(identifier.to_vec())),
+        "bigint" | "bigserial" | "binary" | "bit" | "blob" | "bool" | "boolean" | "bytea"
+        | "char" | "character" | "date" | "datetime" | "decimal" | "double" | "enum" | "float"
+        | "int" | "integer" | "interval" | "json" | "jsonb" | "longtext" | "mediumint"
+        | "numeric" | "precision" | "real" | "serial" | "smallint" | "text" | "time"
+        | "timestamp" | "timestamptz" | "tinyint" | "unsigned" | "uuid" | "varchar" | "varying"
+        | "year" => Ok(Token::TYPE(identifier.to_vec())),
+        _ => Err(String::from("Not a keyword")),
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer, dialect: SqlDialect) {
+    let mut l = Lexer::new(input);
+    l.dialect = dialect;
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct SqlLexer {
+    pub dialect: SqlDialect,
+}
+
+impl lexer::Lexer for SqlLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.dialect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sql() {
+        let rows = highlight(
+            "-- Users\nSELECT \"id\", COUNT(*) AS n, 'it''s' /* a\nb */\nfrom users WHERE age >= 1.5e3 and name = :name OR id = ? IS NOT NULL;",
+            "sql",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["SELECT", "AS", "from", "WHERE", "and", "OR", "IS", "NOT"],
+            "should highlight keywords whatever their case"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["COUNT"],
+            "should highlight functions"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["\"id\""],
+            "should highlight quoted identifiers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["'it''s'"],
+            "should highlight strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["1.5e3", "NULL"],
+            "should highlight numbers and constants"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec![":name", "?"],
+            "should highlight placeholders"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["-- Users", "/* a", "b */"],
+            "should highlight comments"
+        );
+    }
+
+    #[test]
+    fn postgres() {
+        let rows = highlight(
+            "CREATE FUNCTION f() RETURNS int AS $body$\nSELECT 1\n$body$ LANGUAGE sql;\nSELECT E'a\\'b', $1::TEXT WHERE a ILIKE 'x'",
+            "postgres",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["$body$", "SELECT 1", "$body$", "E'a\\'b'", "'x'"],
+            "should highlight dollar-quoted and escape strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["int", "TEXT"],
+            "should highlight types and casts"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["$1"],
+            "should highlight positional placeholders"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["CREATE", "FUNCTION", "RETURNS", "AS", "LANGUAGE", "SELECT", "WHERE", "ILIKE"],
+            "should highlight keywords of the dialect"
+        );
+    }
+
+    #[test]
+    fn mysql_and_sqlite() {
+        let rows = highlight("SELECT `a`, \"b\\\"\" # c\nFROM t WHERE @d", "mysql");
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["`a`"],
+            "should highlight backtick identifiers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"b\\\"\""],
+            "should see double quotes as strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# c"],
+            "should highlight `#` comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["@d"],
+            "should highlight variables"
+        );
+
+        let rows = highlight(
+            "PRAGMA foreign_keys; SELECT [a b] FROM \"t\" WHERE $c",
+            "sqlite",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["[a b]", "\"t\""],
+            "should highlight bracket identifiers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k")[0],
+            "PRAGMA",
+            "should highlight keywords of the dialect"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["$c"],
+            "should highlight named placeholders"
+        );
+    }
+}
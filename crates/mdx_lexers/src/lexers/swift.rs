@@ -0,0 +1,134 @@
+//! Lexer for Swift.
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::c_like::{self, AnnotationSyntax, CLikeSyntax, StringSyntax},
+};
+
+pub const SYNTAX: CLikeSyntax = CLikeSyntax {
+    keywords: &[
+        "actor",
+        "any",
+        "as",
+        "associatedtype",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "convenience",
+        "default",
+        "defer",
+        "deinit",
+        "didSet",
+        "do",
+        "dynamic",
+        "else",
+        "enum",
+        "extension",
+        "fallthrough",
+        "fileprivate",
+        "final",
+        "for",
+        "func",
+        "get",
+        "guard",
+        "if",
+        "import",
+        "in",
+        "indirect",
+        "init",
+        "inout",
+        "internal",
+        "is",
+        "lazy",
+        "let",
+        "mutating",
+        "nonmutating",
+        "open",
+        "operator",
+        "override",
+        "private",
+        "protocol",
+        "public",
+        "repeat",
+        "required",
+        "rethrows",
+        "return",
+        "self",
+        "Self",
+        "set",
+        "some",
+        "static",
+        "struct",
+        "subscript",
+        "super",
+        "switch",
+        "throw",
+        "throws",
+        "try",
+        "typealias",
+        "var",
+        "weak",
+        "where",
+        "while",
+        "willSet",
+    ],
+    types: &[],
+    constants: &["true", "false", "nil"],
+    capitalized_types: true,
+    nested_comments: true,
+    strings: &[
+        StringSyntax::Hashed,
+        StringSyntax::TripleDouble,
+        StringSyntax::Double,
+    ],
+    string_prefixes: &[],
+    annotations: AnnotationSyntax::At,
+    preprocessor: true,
+};
+
+pub struct SwiftLexer;
+
+impl lexer::Lexer for SwiftLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        c_like::tokenize(input, tokens, &SYNTAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn swift() {
+        let rows = highlight(
+            "#if DEBUG\n@MainActor\nfunc greet(_ name: String) -> Int {\n  let raw = #\"a \"b\" \\n\"#\n  let c = \"c\"\n  return nil ?? 0\n}\n#endif",
+            "swift",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["#if", "@MainActor", "#endif"],
+            "should highlight directives and attributes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["#\"a \"b\" \\n\"#", "\"c\""],
+            "should highlight raw strings between hashes"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["String", "Int"],
+            "should highlight capitalized types"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["nil", "0"],
+            "should highlight constants"
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Open inline tables (`{`) and arrays (`[`).
+    stack: Vec<char>,
+    /// Whether the lexer is in a table header (`[a]`, `[[a]]`).
+    in_header: bool,
+}
+
+fn is_bare_key_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            stack: vec![],
+            in_header: false,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Last character before `position` that is not whitespace, on the same
+    /// line.
+    fn previous_char(&self, position: usize) -> char {
+        self.input[..position]
+            .iter()
+            .rev()
+            .find(|ch| !matches!(ch, ' ' | '\t'))
+            .copied()
+            .unwrap_or('\n')
+    }
+
+    /// Whether a key may start at `position`: at the start of a line, after a
+    /// `.` in a dotted key, or after `{` or `,` in an inline table.
+    fn is_key_position(&self, position: usize) -> bool {
+        match self.previous_char(position) {
+            '\n' => self.stack.is_empty(),
+            '.' => true,
+            '{' | ',' => self.stack.last() == Some(&'{'),
+            _ => false,
+        }
+    }
+
+    /// Read a string; multi-line strings (`"""a"""`) may span several lines.
+    fn read_string(&mut self) -> Vec<char> {
+        let position = self.position;
+        let quote = self.ch;
+        let multiline = self.peek_char(1) == quote && self.peek_char(2) == quote;
+        if multiline {
+            self.read_char();
+            self.read_char();
+        }
+        self.read_char();
+        while self.position < self.input.len() && (multiline || self.ch != '\n') {
+            if quote == '"' && self.ch == '\\' {
+                self.read_char();
+            } else if self.ch == quote
+                && (!multiline || (self.peek_char(1) == quote && self.peek_char(2) == quote))
+            {
+                break;
+            }
+            self.read_char();
+        }
+        if multiline {
+            self.read_char();
+            self.read_char();
+            // Up to two quotes can end the content (`""""a"""""`)
+            while self.peek_char(1) == quote {
+                self.read_char();
+            }
+        }
+        if self.ch == quote {
+            self.read_char();
+        }
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read a value that is not a string: a number, date, time, or boolean.
+    fn read_value(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len()
+            && (is_bare_key_char(self.ch) || matches!(self.ch, '.' | ':' | '+'))
+        {
+            self.read_char();
+        }
+        // Space between a date and a time (`1979-05-27 07:32:00`)
+        if self.ch == ' '
+            && self.peek_char(1).is_ascii_digit()
+            && self.peek_char(3) == ':'
+            && is_date(&self.input[position..self.position])
+        {
+            self.read_char();
+            while self.position < self.input.len()
+                && (is_bare_key_char(self.ch) || matches!(self.ch, '.' | ':' | '+'))
+            {
+                self.read_char();
+            }
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+
+        match self.ch {
+            '\n' => {
+                self.in_header = false;
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '#' => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::COMMENT(self.input[position..self.position].to_vec())
+            }
+            '[' if self.previous_char(position) == '\n' && self.stack.is_empty() => {
+                self.in_header = true;
+                self.read_char();
+                if self.ch == '[' {
+                    self.read_char();
+                }
+                Token::PUNCTUATION(self.input[position..self.position].to_vec())
+            }
+            ']' if self.in_header => {
+                self.in_header = false;
+                self.read_char();
+                if self.ch == ']' {
+                    self.read_char();
+                }
+                Token::PUNCTUATION(self.input[position..self.position].to_vec())
+            }
+            '[' | '{' => {
+                self.stack.push(self.ch);
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            ']' | '}' => {
+                self.stack.pop();
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            '=' | ',' | '.' => {
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            '"' | '\'' => {
+                let value = self.read_string();
+                if self.in_header {
+                    Token::ENTITY(value)
+                } else if self.is_key_position(position) {
+                    Token::ATTRIBUTE(value)
+                } else {
+                    Token::STRING(value)
+                }
+            }
+            ch if self.in_header && is_bare_key_char(ch) => {
+                while self.position < self.input.len() && is_bare_key_char(self.ch) {
+                    self.read_char();
+                }
+                Token::ENTITY(self.input[position..self.position].to_vec())
+            }
+            ch if self.is_key_position(position) && is_bare_key_char(ch) => {
+                while self.position < self.input.len() && is_bare_key_char(self.ch) {
+                    self.read_char();
+                }
+                Token::ATTRIBUTE(self.input[position..self.position].to_vec())
+            }
+            ch if is_bare_key_char(ch) || ch == '+' => {
+                let value = self.read_value();
+                let name: String = value.iter().collect();
+                let unsigned = name.strip_prefix(['-', '+']).unwrap_or(&name);
+                match unsigned {
+                    "true" | "false" | "inf" | "nan" => Token::CONSTANT(value),
+                    _ if is_date(&value) || name.contains(':') => Token::CONSTANT(value),
+                    _ if unsigned.starts_with(|ch: char| ch.is_ascii_digit()) => Token::INT(value),
+                    _ => Token::IDENT(value),
+                }
+            }
+            ch => {
+                self.read_char();
+                Token::CH(ch)
+            }
+        }
+    }
+}
+
+/// Whether `value` starts with a date (`1979-05-27`).
+fn is_date(value: &[char]) -> bool {
+    value.len() >= 10
+        && value[..10].iter().enumerate().all(|(index, ch)| {
+            if index == 4 || index == 7 {
+                *ch == '-'
+            } else {
+                ch.is_ascii_digit()
+            }
+        })
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct TomlLexer;
+
+impl lexer::Lexer for TomlLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn toml() {
+        let rows = highlight(
+            "# Config\ntitle = \"TOML\"\n[owner.\"a b\"]\ndob = 1979-05-27 07:32:00-08:00\nsite.\"google.com\" = true\n[[products]]\npoint = { x = 1_000, y = -2.5e3 }\ncolors = [0xff, inf, 'red']",
+            "toml",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# Config"],
+            "should highlight comments"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-en"),
+            vec!["owner", "\"a b\"", "products"],
+            "should highlight tables and arrays of tables"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec![
+                "title",
+                "dob",
+                "site",
+                "\"google.com\"",
+                "point",
+                "x",
+                "y",
+                "colors"
+            ],
+            "should highlight dotted and inline keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"TOML\"", "'red'"],
+            "should highlight strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec![
+                "1979-05-27 07:32:00-08:00",
+                "true",
+                "1_000",
+                "-2.5e3",
+                "0xff",
+                "inf"
+            ],
+            "should highlight datetimes, booleans and numbers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-p"),
+            vec![
+                "=", "[", ".", "]", "=", ".", "=", "[[", "]]", "=", "{", "=", ",", "=", "}", "=",
+                "[", ",", ",", "]"
+            ],
+            "should highlight punctuation"
+        );
+    }
+
+    #[test]
+    fn multiline_strings() {
+        let rows = highlight("a = \"\"\"\nb \\\"\"\"\nc\"\"\"\nd = '''e\n'''", "toml");
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["\"\"\"", "b \\\"\"\"", "c\"\"\"", "'''e", "'''"],
+            "should highlight multi-line strings on each row"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["a", "d"],
+            "should highlight keys after multi-line strings"
+        );
+        assert_eq!(rows.len(), 5, "should keep rows");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer,
+    lexers::javascript::{self, Lexer},
+    token::Token,
+};
+
+fn is_primitive_type(id: &str) -> bool {
+    matches!(
+        id,
+        "string" | "number" | "boolean" | "any" | "unknown" | "never" | "object" | "symbol"
+            | "bigint"
+    )
+}
+
+pub fn get_keyword_token(identifier: &[char]) -> Result<Token, String> {
+    let id: String = identifier.iter().collect();
+    match &id[..] {
+        "abstract" | "as" | "asserts" | "declare" | "infer" | "is" | "keyof" | "module"
+        | "namespace" | "override" | "readonly" | "satisfies" | "type" | "unique" => {
+            Ok(Token::KEYWORD(identifier.to_vec()))
+        }
+        _ => Err(String::from("Not a keyword")),
+    }
+}
+
+/// Whether the identifier at `position` is a type, based on the keyword or
+/// the punctuation before it.
+fn is_type_position(l: &Lexer, position: usize, identifier: &[char]) -> bool {
+    let id: String = identifier.iter().collect();
+    match &l.previous_word(position)[..] {
+        "interface" | "type" | "enum" | "namespace" | "class" | "extends" | "implements" => {
+            return true
+        }
+        "as" | "satisfies" | "keyof" | "is" | "readonly" | "infer" => {
+            return is_primitive_type(&id) || id.starts_with(char::is_uppercase)
+        }
+        _ => {}
+    }
+    match l.previous_char(position) {
+        Some(':' | '<' | '|' | '&') => {
+            is_primitive_type(&id) || id.starts_with(char::is_uppercase)
+        }
+        _ => false,
+    }
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer, jsx: bool) {
+    let mut l = Lexer::new(input);
+    l.jsx = jsx;
+    l.lex_expression = tokenize_lexer;
+    l.read_char();
+    tokenize_lexer(&mut l, tokens);
+}
+
+fn tokenize_lexer(l: &mut Lexer, tokens: &mut HighlighterTokenizer) {
+    loop {
+        let start_position = l.position;
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        let token = match token {
+            Token::IDENT(value) => match get_keyword_token(&value) {
+                Ok(keyword_token) => keyword_token,
+                Err(_) if is_type_position(l, start_position, &value) => Token::TYPE(value),
+                Err(_) => Token::IDENT(value),
+            },
+            Token::ENTITY(value) if is_type_position(l, start_position, &value) => {
+                Token::TYPE(value)
+            }
+            token => token,
+        };
+        javascript::add_token(l, token, tokens);
+    }
+}
+
+pub struct TypescriptLexer {
+    pub jsx: bool,
+}
+
+impl lexer::Lexer for TypescriptLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens, self.jsx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn typescript() {
+        let rows = highlight(
+            "@Component()\nexport abstract class Foo<T> implements Bar {\n  private readonly name: string;\n}\ntype Id = keyof Foo;",
+            "ts",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-k"),
+            vec!["export", "abstract", "class", "implements", "private", "readonly", "type", "keyof"],
+            "should highlight TypeScript keywords"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["Foo", "T", "Bar", "string", "Id", "Foo"],
+            "should highlight type names"
+        );
+        assert_eq!(with_class(&rows, "hl-m"), vec!["@Component"], "should highlight decorators");
+        assert_eq!(with_class(&rows, "hl-en"), vec!["name"], "should keep entities");
+    }
+
+    #[test]
+    fn tsx() {
+        let rows = highlight("const a = <button onClick={(e) => go(e)} kind=\"primary\">Hi</button>;", "tsx");
+        assert_eq!(
+            with_class(&rows, "hl-ent"),
+            vec!["button", "button"],
+            "should highlight tag names"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["onClick", "kind"],
+            "should highlight attribute names"
+        );
+        assert_eq!(with_class(&rows, "hl-en"), vec!["go"], "should lex expressions");
+
+        let rows = highlight("const a = <button>Hi</button>;", "ts");
+        assert!(with_class(&rows, "hl-ent").is_empty(), "should not lex JSX in `ts`");
+    }
+
+    #[test]
+    fn tsx_expressions() {
+        let rows = highlight(
+            "<List items={rows as Row[]} render={(row: Row) => <Item {...row} />} />",
+            "tsx",
+        );
+        assert_eq!(with_class(&rows, "hl-k"), vec!["as"], "should highlight keywords in expressions");
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["Row", "Row"],
+            "should highlight types in expressions"
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use crate::{highlighter_tokenizer::HighlighterTokenizer, lexer, lexers, token::Token};
+
+pub struct Lexer {
+    input: Vec<char>,
+    pub position: usize,
+    pub read_position: usize,
+    pub ch: char,
+    /// Depth of flow collections (`[a]`, `{a: b}`).
+    flow_depth: usize,
+    /// Indent of the line with a block scalar (`|`, `>`) whose content starts
+    /// on the next line.
+    block_scalar_indent: Option<usize>,
+}
+
+/// Whether `ch` is a flow indicator, which ends plain scalars in flow
+/// collections.
+fn is_flow_indicator(ch: char) -> bool {
+    matches!(ch, ',' | '[' | ']' | '{' | '}')
+}
+
+impl Lexer {
+    pub fn new(input: Vec<char>) -> Self {
+        Self {
+            input,
+            position: 0,
+            read_position: 0,
+            ch: '\0',
+            flow_depth: 0,
+            block_scalar_indent: None,
+        }
+    }
+
+    pub fn read_char(&mut self) {
+        if self.read_position >= self.input.len() {
+            self.ch = '\0';
+        } else {
+            self.ch = self.input[self.read_position];
+        }
+        self.position = self.read_position;
+        self.read_position += 1;
+    }
+
+    fn peek_char(&self, offset: usize) -> char {
+        let position = self.position + offset;
+        if position >= self.input.len() {
+            '\0'
+        } else {
+            self.input[position]
+        }
+    }
+
+    /// Whether `ch` ends an indicator (`- `, `: `) or a plain scalar.
+    fn is_separator(&self, ch: char) -> bool {
+        ch == '\0' || ch.is_whitespace() || (self.flow_depth > 0 && is_flow_indicator(ch))
+    }
+
+    fn is_line_start(&self, position: usize) -> bool {
+        position == 0 || self.input[position - 1] == '\n'
+    }
+
+    /// Number of spaces at the start of the line of `position`.
+    fn line_indent(&self, position: usize) -> usize {
+        let mut start = position.min(self.input.len());
+        while start > 0 && self.input[start - 1] != '\n' {
+            start -= 1;
+        }
+        self.input[start..]
+            .iter()
+            .take_while(|ch| **ch == ' ')
+            .count()
+    }
+
+    /// Whether a document marker (`---`, `...`) starts at the current
+    /// position.
+    fn is_document_marker(&self) -> bool {
+        self.is_line_start(self.position)
+            && matches!(self.ch, '-' | '.')
+            && self.peek_char(1) == self.ch
+            && self.peek_char(2) == self.ch
+            && self.is_separator(self.peek_char(3))
+    }
+
+    /// Read the content of a block scalar: the following lines that are blank
+    /// or as indented as its first line, which is more indented than the line
+    /// with its indicator.
+    fn read_block_scalar(&mut self, indent: usize) -> Vec<char> {
+        let position = self.position;
+        let mut end = position;
+        let mut content_indent = None;
+        while self.position < self.input.len() {
+            let line_start = self.position;
+            while self.position < self.input.len() && self.ch == ' ' {
+                self.read_char();
+            }
+            let blank = self.position >= self.input.len() || self.ch == '\n';
+            if !blank {
+                let line_indent = self.position - line_start;
+                let content_indent = *content_indent.get_or_insert(line_indent);
+                if line_indent <= indent || line_indent < content_indent {
+                    break;
+                }
+            }
+            while self.position < self.input.len() && self.ch != '\n' {
+                self.read_char();
+            }
+            if !blank {
+                end = self.position;
+            }
+            self.read_char();
+        }
+        self.read_position = end;
+        self.read_char();
+        self.input[position..end].to_vec()
+    }
+
+    /// Read a quoted scalar, which may span several lines.
+    fn read_quoted(&mut self) -> Vec<char> {
+        let position = self.position;
+        let quote = self.ch;
+        self.read_char();
+        while self.position < self.input.len() {
+            // Escapes (`'a''b'`, `"a\"b"`)
+            if (quote == '\'' && self.ch == '\'' && self.peek_char(1) == '\'')
+                || (quote == '"' && self.ch == '\\')
+            {
+                self.read_char();
+            } else if self.ch == quote {
+                break;
+            }
+            self.read_char();
+        }
+        self.read_char();
+        self.input[position..self.position.min(self.input.len())].to_vec()
+    }
+
+    /// Read a plain scalar, up to the end of the line, a comment, or `: `.
+    fn read_plain(&mut self) -> Vec<char> {
+        let position = self.position;
+        while self.position < self.input.len() && self.ch != '\n' {
+            if self.ch == ':' && self.is_separator(self.peek_char(1)) {
+                break;
+            }
+            if self.ch == '#' && self.input[self.position - 1].is_whitespace() {
+                break;
+            }
+            if self.flow_depth > 0 && is_flow_indicator(self.ch) {
+                break;
+            }
+            self.read_char();
+        }
+        let mut end = self.position.min(self.input.len());
+        while end > position && self.input[end - 1].is_whitespace() {
+            end -= 1;
+        }
+        self.read_position = end;
+        self.read_char();
+        self.input[position..end].to_vec()
+    }
+
+    /// Read a word: an anchor, alias, or tag.
+    fn read_word(&mut self) -> Vec<char> {
+        let position = self.position;
+        while !self.is_separator(self.ch) {
+            self.read_char();
+        }
+        self.input[position..self.position].to_vec()
+    }
+
+    /// Whether the scalar ending at the current position is a key.
+    fn is_key(&self) -> bool {
+        let mut position = self.position;
+        while position < self.input.len() && matches!(self.input[position], ' ' | '\t') {
+            position += 1;
+        }
+        position < self.input.len()
+            && self.input[position] == ':'
+            && (position + 1 >= self.input.len()
+                || self.input[position + 1].is_whitespace()
+                || (self.flow_depth > 0 && is_flow_indicator(self.input[position + 1])))
+    }
+
+    pub fn next_token(&mut self) -> Token {
+        let position = self.position;
+
+        if self.position >= self.input.len() {
+            return Token::EOF;
+        }
+        if self.is_line_start(position) {
+            if let Some(indent) = self.block_scalar_indent.take() {
+                let content = self.read_block_scalar(indent);
+                if !content.is_empty() {
+                    return Token::STRING(content);
+                }
+            }
+        }
+
+        match self.ch {
+            '\n' => {
+                self.read_char();
+                Token::ENDL('\n')
+            }
+            ch if ch.is_whitespace() => {
+                self.read_char();
+                Token::CH(ch)
+            }
+            '#' if position == 0 || self.input[position - 1].is_whitespace() => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::COMMENT(self.input[position..self.position].to_vec())
+            }
+            '%' if self.is_line_start(position) => {
+                while self.position < self.input.len() && self.ch != '\n' {
+                    self.read_char();
+                }
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            _ if self.is_document_marker() => {
+                self.read_char();
+                self.read_char();
+                self.read_char();
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            '-' | '?' | ':' if self.is_separator(self.peek_char(1)) => {
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            '[' | '{' => {
+                self.flow_depth += 1;
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            ']' | '}' => {
+                self.flow_depth = self.flow_depth.saturating_sub(1);
+                self.read_char();
+                Token::PUNCTUATION(vec![self.input[position]])
+            }
+            ',' if self.flow_depth > 0 => {
+                self.read_char();
+                Token::PUNCTUATION(vec![','])
+            }
+            '&' | '*' if !self.is_separator(self.peek_char(1)) => Token::VAR(self.read_word()),
+            '!' => Token::TYPE(self.read_word()),
+            '|' | '>' if self.flow_depth == 0 => {
+                self.read_char();
+                while matches!(self.ch, '-' | '+') || self.ch.is_ascii_digit() {
+                    self.read_char();
+                }
+                self.block_scalar_indent = Some(self.line_indent(position));
+                Token::META(self.input[position..self.position].to_vec())
+            }
+            '"' | '\'' => {
+                let value = self.read_quoted();
+                if self.is_key() {
+                    Token::ATTRIBUTE(value)
+                } else {
+                    Token::STRING(value)
+                }
+            }
+            _ => {
+                let value = self.read_plain();
+                if value.is_empty() {
+                    self.read_char();
+                    return Token::CH(self.input[position]);
+                }
+                if self.is_key() {
+                    return Token::ATTRIBUTE(value);
+                }
+                match scalar_kind(&value.iter().collect::<String>()) {
+                    Some(token) => token(value),
+                    None => Token::STRING(value),
+                }
+            }
+        }
+    }
+}
+
+/// Kind of a plain scalar that is not a string (`null`, `true`, `1.5`).
+fn scalar_kind(value: &str) -> Option<fn(Vec<char>) -> Token> {
+    match value {
+        "~" | "null" | "Null" | "NULL" | "true" | "True" | "TRUE" | "false" | "False" | "FALSE"
+        | ".inf" | "-.inf" | "+.inf" | ".Inf" | ".INF" | ".nan" | ".NaN" | ".NAN" => {
+            return Some(Token::CONSTANT)
+        }
+        _ => {}
+    }
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+    if let Some(digits) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0o"))
+    {
+        return if !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            Some(Token::INT)
+        } else {
+            None
+        };
+    }
+    if unsigned.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.')
+        && unsigned.parse::<f64>().is_ok()
+    {
+        return Some(Token::INT);
+    }
+    None
+}
+
+pub fn tokenize(input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+    let mut l = Lexer::new(input);
+    l.read_char();
+    loop {
+        let token = l.next_token();
+        if token == Token::EOF {
+            break;
+        }
+
+        lexers::add_token(tokens, token);
+    }
+}
+
+pub struct YamlLexer;
+
+impl lexer::Lexer for YamlLexer {
+    fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+        tokenize(input, tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{highlight, with_class};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn yaml() {
+        let rows = highlight(
+            "%YAML 1.2\n---\ntitle: Hello:world # comment\nbase: &base\n  \"draft\": !!bool false\n  tags: [a, 'b c', 1.5]\npost:\n  <<: *base\n  count: 0x1F\n...",
+            "yaml",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["%YAML 1.2", "---", "..."],
+            "should highlight directives and document markers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["title", "base", "\"draft\"", "tags", "post", "<<", "count"],
+            "should highlight keys"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["Hello:world", "a", "'b c'"],
+            "should highlight strings"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-c"),
+            vec!["false", "1.5", "0x1F"],
+            "should highlight constants and numbers"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-v"),
+            vec!["&base", "*base"],
+            "should highlight anchors"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-t"),
+            vec!["!!bool"],
+            "should highlight tags"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            vec!["# comment"],
+            "should highlight comments"
+        );
+    }
+
+    #[test]
+    fn block_scalars() {
+        let rows = highlight(
+            "- run: |-\n    echo a # b\n\n    echo c\n  name: >\n    d\n- e",
+            "yml",
+        );
+        assert_eq!(
+            with_class(&rows, "hl-m"),
+            vec!["|-", ">"],
+            "should highlight block scalar indicators"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-s"),
+            vec!["    echo a # b", "    echo c", "    d", "e"],
+            "should highlight block scalars on each row"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-cmt"),
+            Vec::<String>::new(),
+            "should not see comments in block scalars"
+        );
+        assert_eq!(
+            with_class(&rows, "hl-a"),
+            vec!["run", "name"],
+            "should highlight keys after block scalars"
+        );
+        assert_eq!(rows.len(), 7, "should keep rows");
+    }
+}
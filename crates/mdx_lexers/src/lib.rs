@@ -1,15 +1,54 @@
+mod class_names;
 mod highlighter_tokenizer;
+mod lexer;
+mod line_kind;
+mod registry;
+mod theme;
 mod token;
+mod token_kind;
 mod lexers;
 pub mod constants;
+#[cfg(test)]
+mod test_utils;
 
 use mdxjs::hast;
 
-use crate::lexers::{javascript, raw};
+pub use crate::class_names::ClassNames;
+pub use crate::highlighter_tokenizer::{HighlighterTokenizer, LineProperties};
+pub use crate::lexer::Lexer;
+pub use crate::line_kind::LineKind;
+pub use crate::lexers::{
+    c::CLexer,
+    cpp::CppLexer,
+    csharp::CsharpLexer,
+    css::CssLexer,
+    diff::DiffLexer,
+    dockerfile::DockerfileLexer,
+    go::GoLexer,
+    graphql::GraphqlLexer,
+    html::HtmlLexer,
+    ini::IniLexer,
+    java::JavaLexer,
+    javascript::JavascriptLexer,
+    json::{JsonDialect, JsonLexer},
+    kotlin::KotlinLexer,
+    makefile::MakefileLexer,
+    markdown::MarkdownLexer,
+    python::PythonLexer,
+    raw::RawLexer,
+    rust::RustLexer,
+    shell::ShellLexer,
+    sql::{SqlDialect, SqlLexer},
+    swift::SwiftLexer,
+    toml::TomlLexer,
+    typescript::TypescriptLexer,
+    yaml::YamlLexer,
+};
+pub use crate::registry::LanguageRegistry;
+pub use crate::theme::{Theme, TokenStyle};
+pub use crate::token_kind::TokenKind;
 
+/// Highlight `input` with the built-in lexer of `lang`.
 pub fn highlight(input: Vec<char>, lang: &str) -> Vec<hast::Node> {
-    match lang {
-        "javascript" | "js" => javascript::highlight(input),
-        _ => raw::highlight(input),
-    }
+    LanguageRegistry::default().highlight(input, lang)
 }
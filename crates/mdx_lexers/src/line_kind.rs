@@ -0,0 +1,24 @@
+/// Kind of a line, which the tokenizer adds to the class name or style of its
+/// row.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase"))]
+pub enum LineKind {
+    /// Lines added in diffs.
+    Added,
+    /// Lines removed in diffs.
+    Removed,
+}
+
+impl LineKind {
+    /// Every kind of line.
+    pub const ALL: [LineKind; 2] = [LineKind::Added, LineKind::Removed];
+
+    /// Class name of the kind, without the prefixed line class name.
+    pub fn class_suffix(self) -> &'static str {
+        match self {
+            LineKind::Added => "add",
+            LineKind::Removed => "del",
+        }
+    }
+}
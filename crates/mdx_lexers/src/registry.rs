@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use mdxjs::hast;
+
+use crate::{
+    highlighter_tokenizer::HighlighterTokenizer,
+    lexer::Lexer,
+    lexers::{
+        c::CLexer,
+        cpp::CppLexer,
+        csharp::CsharpLexer,
+        css::CssLexer,
+        diff::{self, DiffLexer},
+        dockerfile::DockerfileLexer,
+        go::GoLexer,
+        graphql::GraphqlLexer,
+        html::HtmlLexer,
+        ini::IniLexer,
+        java::JavaLexer,
+        javascript::JavascriptLexer,
+        json::{JsonDialect, JsonLexer},
+        kotlin::KotlinLexer,
+        makefile::MakefileLexer,
+        markdown::MarkdownLexer,
+        python::PythonLexer,
+        raw::RawLexer,
+        rust::RustLexer,
+        shell::ShellLexer,
+        sql::{SqlDialect, SqlLexer},
+        swift::SwiftLexer,
+        toml::TomlLexer,
+        typescript::TypescriptLexer,
+        yaml::YamlLexer,
+    },
+};
+
+/// Maps language names and aliases to lexers.
+///
+/// Languages without a registered lexer are highlighted with the raw lexer.
+pub struct LanguageRegistry {
+    lexers: HashMap<String, Rc<dyn Lexer>>,
+    fallback: Rc<dyn Lexer>,
+}
+
+impl LanguageRegistry {
+    /// Registry without any language.
+    pub fn new() -> Self {
+        Self {
+            lexers: HashMap::new(),
+            fallback: Rc::new(RawLexer),
+        }
+    }
+
+    /// Register `lexer` for every name in `names`.
+    ///
+    /// Names are case-insensitive and replace a lexer registered before with
+    /// the same name.
+    pub fn register<L: Lexer + 'static>(&mut self, names: &[&str], lexer: L) {
+        let lexer: Rc<dyn Lexer> = Rc::new(lexer);
+        for name in names {
+            self.lexers.insert(name.to_lowercase(), lexer.clone());
+        }
+    }
+
+    /// Get the lexer registered for `lang`.
+    pub fn get(&self, lang: &str) -> Option<&dyn Lexer> {
+        self.lexers.get(&lang.to_lowercase()).map(|lexer| lexer.as_ref())
+    }
+
+    /// Get the lexer registered for the language of a diff (`diff-ts`).
+    fn get_diff_language(&self, lang: &str) -> Option<&dyn Lexer> {
+        let lang = lang.to_lowercase();
+        self.get(lang.strip_prefix("diff-")?)
+    }
+
+    /// Highlight `input` with the lexer registered for `lang`, one row per
+    /// line of code.
+    ///
+    /// Languages that are not registered but start with `diff-` are
+    /// highlighted as diffs of the rest of the name (`diff-ts`).
+    pub fn highlight(&self, input: Vec<char>, lang: &str) -> Vec<hast::Node> {
+        self.highlight_with(input, lang, HighlighterTokenizer::new())
+    }
+
+    /// Like [`highlight`][Self::highlight], with a configured tokenizer.
+    pub fn highlight_with(
+        &self,
+        input: Vec<char>,
+        lang: &str,
+        mut tokens: HighlighterTokenizer,
+    ) -> Vec<hast::Node> {
+        self.lex(input, lang, &mut tokens);
+        tokens.end_of_line();
+        tokens.get_highlighted_code()
+    }
+
+    /// Lex `input` with the lexer registered for `lang` into `tokens`,
+    /// without closing the last row, so that lexers can hand code in other
+    /// languages to the registry.
+    pub fn lex(&self, input: Vec<char>, lang: &str, tokens: &mut HighlighterTokenizer) {
+        match self.get(lang) {
+            Some(lexer) => lexer.lex_with(input, tokens, self),
+            // Diffs of a registered language (`diff-ts`)
+            None => match self.get_diff_language(lang) {
+                Some(lexer) => diff::tokenize_language(input, tokens, lexer),
+                None => self.fallback.lex(input, tokens),
+            },
+        }
+    }
+}
+
+impl Default for LanguageRegistry {
+    /// Registry with the built-in lexers.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(&["javascript", "js", "jsx", "mjs", "cjs"], JavascriptLexer);
+        registry.register(
+            &["typescript", "ts", "mts", "cts"],
+            TypescriptLexer { jsx: false },
+        );
+        registry.register(&["tsx"], TypescriptLexer { jsx: true });
+        registry.register(&["python", "py"], PythonLexer);
+        registry.register(&["css"], CssLexer { line_comments: false });
+        registry.register(&["scss", "less"], CssLexer { line_comments: true });
+        registry.register(&["html", "htm", "xhtml", "xml", "svg"], HtmlLexer);
+        registry.register(&["json"], JsonLexer { dialect: JsonDialect::Json, flag_errors: false });
+        registry.register(&["jsonc"], JsonLexer { dialect: JsonDialect::Jsonc, flag_errors: false });
+        registry.register(&["json5"], JsonLexer { dialect: JsonDialect::Json5, flag_errors: false });
+        registry.register(&["rust", "rs"], RustLexer);
+        registry.register(
+            &["bash", "sh", "zsh", "shell", "shellscript"],
+            ShellLexer { console: false },
+        );
+        registry.register(&["console", "shell-session"], ShellLexer { console: true });
+        registry.register(&["yaml", "yml"], YamlLexer);
+        registry.register(&["diff", "patch"], DiffLexer);
+        registry.register(&["sql"], SqlLexer { dialect: SqlDialect::Standard });
+        registry.register(
+            &["postgres", "postgresql", "psql", "pgsql"],
+            SqlLexer { dialect: SqlDialect::Postgres },
+        );
+        registry.register(&["mysql", "mariadb"], SqlLexer { dialect: SqlDialect::Mysql });
+        registry.register(&["sqlite", "sqlite3"], SqlLexer { dialect: SqlDialect::Sqlite });
+        registry.register(&["toml"], TomlLexer);
+        registry.register(&["go", "golang"], GoLexer);
+        registry.register(&["java"], JavaLexer);
+        registry.register(&["kotlin", "kt", "kts"], KotlinLexer);
+        registry.register(&["c", "h"], CLexer);
+        registry.register(&["cpp", "c++", "cc", "cxx", "hpp", "hh"], CppLexer);
+        registry.register(&["csharp", "cs", "c#"], CsharpLexer);
+        registry.register(&["swift"], SwiftLexer);
+        registry.register(&["markdown", "md"], MarkdownLexer { mdx: false });
+        registry.register(&["mdx"], MarkdownLexer { mdx: true });
+        registry.register(&["graphql", "gql"], GraphqlLexer);
+        registry.register(&["dockerfile", "docker", "containerfile"], DockerfileLexer);
+        registry.register(&["makefile", "make", "mk"], MakefileLexer);
+        registry.register(&["ini", "cfg", "dosini"], IniLexer { dotenv: false });
+        registry.register(&["dotenv", "env"], IniLexer { dotenv: true });
+        registry.register(&["raw", "text", "txt", "plain"], RawLexer);
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::to_code;
+    use pretty_assertions::assert_eq;
+
+    struct UpperLexer;
+
+    impl Lexer for UpperLexer {
+        fn lex(&self, input: Vec<char>, tokens: &mut HighlighterTokenizer) {
+            tokens.add_text(input.iter().collect::<String>().to_uppercase());
+        }
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = LanguageRegistry::default();
+        assert!(registry.get("js").is_some(), "should register built-in lexers");
+        assert!(registry.get("JS").is_some(), "should ignore the case of names");
+        assert!(registry.get("upper").is_none(), "should not know other languages");
+
+        registry.register(&["upper", "up"], UpperLexer);
+        let rows = registry.highlight("a".chars().collect(), "up");
+        assert_eq!(
+            rows.iter().map(ToString::to_string).collect::<String>(),
+            "A",
+            "should highlight with registered lexers"
+        );
+
+        let rows = LanguageRegistry::new().highlight("a\nb".chars().collect(), "upper");
+        assert_eq!(rows.len(), 2, "should fall back to the raw lexer");
+    }
+
+    #[test]
+    fn round_trip() {
+        let registry = LanguageRegistry::default();
+        let samples = [
+            "",
+            "0",
+            "a\n",
+            "if (a < b && c > d) { return '<div>' }\n",
+            "/* a\n * b\n */\n// <c>\nconst d = `e\n${f}\n`;\n",
+            "const a = <A b=\"c\" d={e > 1}>\n  f & g {h}\n</A>;\n",
+            "<>&lt;a&gt;</> /\n*\n",
+            "'unterminated\n\"also",
+            "😀 ünïcödé\t\r\n",
+            "/* a /* b */\n c */ r#\"d\n\"# 'e 'f' b'\\n' #[g(\"h]\")]\n",
+            "f'{a!r:{b}} {{' f\"\"\"\n{c\n}\"\"\" f'{d\n",
+            "a[b=c]::d{e:f(--g,#h)!i;@j k{l:url(m)}}#{n} /* o\n",
+            "<a b='c\nd' e=f/><script>x</SCRIPT><style>\n<![CDATA[ &g <!-- h\n",
+            "{\"a\": [1, 'b', c:, +-.e5, 0x],, /* d\n",
+            "$ a \"$(b `c` ${d)\" <<-'E' \\\n> e\n\tE\nf\n",
+            "--- a: |+\n  b\n\n[c.\"d\"]\ne = \"\"\"f\n'''g\n",
+            "--- a\n+++ b\n@@ -1 +1,2 @@\n-c\n+d\n+\n\\ e\n@@ f",
+            "SELECT $a$ b\n$a$, E'c\\'::d, `e`, [f -- g\n/* h",
+            "#include <a\n@\"b\"\"\nc\" R\"d(\ne)\" 1'0 #\"f\"\n\"\"\"g /* h /* i */\n",
+            "---\na: b\n---\n# c *d\n- [ ] <e f={g}>\n  ```h\n  i\n  ```\n</e> {j\n> ~~~\n",
+            "query ($a: [B!] = {c: D}) { ...e ... on F @g(h: \"\"\"i\n\\\"\"\"\") } }}} ((( \"j",
+            "FROM a AS b\nRUN --c=d \"$e\" \\\n  f ${g:-h}\nENV i=\"j\nk\" \\\n",
+            "a: b ; c $$d\n\t@$(e $(f),$@) ${g\ndefine h\n\ti: $(\nendef\n[k\n",
+            "[a\nb = \"c ; d\nexport e=\"$f ${g\nh\" 'i\n",
+            "\"C:\\",
+            "-a = 'b\\\n+c = `d\\",
+            "b'\nb'",
+            "b'\\",
+        ];
+
+        for lang in registry.lexers.keys() {
+            for lang in [lang.clone(), format!("diff-{}", lang)] {
+                for sample in samples {
+                    assert_eq!(
+                        to_code(&registry.highlight(sample.chars().collect(), &lang)),
+                        sample,
+                        "should round-trip `{}` code",
+                        lang
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::LanguageRegistry;
+use mdxjs::hast;
+
+/// Highlight `code` as `lang` with the default languages.
+pub fn highlight(code: &str, lang: &str) -> Vec<hast::Node> {
+    LanguageRegistry::default().highlight(code.chars().collect(), lang)
+}
+
+/// Class name of `node`, or an empty string.
+pub fn class_name(node: &hast::Node) -> String {
+    match node {
+        hast::Node::Element(element) => element
+            .properties
+            .iter()
+            .find(|(name, _)| name == "className")
+            .map(|(_, value)| match value {
+                hast::PropertyValue::String(value) => value.clone(),
+                _ => String::new(),
+            })
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Flatten highlighted rows into `(class name, text)` pairs, using an empty
+/// class name for plain text and `\n` for the end of each row.
+pub fn to_tokens(rows: &[hast::Node]) -> Vec<(String, String)> {
+    let mut tokens = vec![];
+    for row in rows {
+        for child in row.children().unwrap() {
+            tokens.push((class_name(child), child.to_string()));
+        }
+        tokens.push((String::new(), "\n".into()));
+    }
+    tokens
+}
+
+/// Text of the tokens with class name `class_name`.
+pub fn with_class(rows: &[hast::Node], class_name: &str) -> Vec<String> {
+    to_tokens(rows)
+        .into_iter()
+        .filter(|(name, _)| name == class_name)
+        .map(|(_, value)| value)
+        .collect()
+}
+
+/// Class names of highlighted rows.
+pub fn row_class_names(rows: &[hast::Node]) -> Vec<String> {
+    rows.iter().map(class_name).collect()
+}
+
+/// Text of highlighted rows, joined with line endings.
+pub fn to_code(rows: &[hast::Node]) -> String {
+    rows.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
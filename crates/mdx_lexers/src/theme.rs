@@ -0,0 +1,223 @@
+use crate::{line_kind::LineKind, token_kind::TokenKind};
+
+/// Colour and font style of a kind of token.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase", default))]
+pub struct TokenStyle {
+    /// CSS colour (example: `"#cf222e"`).
+    pub color: String,
+    pub italic: bool,
+    pub bold: bool,
+}
+
+impl TokenStyle {
+    fn new(color: &str) -> Self {
+        Self {
+            color: color.into(),
+            italic: false,
+            bold: false,
+        }
+    }
+
+    fn italic(color: &str) -> Self {
+        Self {
+            italic: true,
+            ..Self::new(color)
+        }
+    }
+
+    /// CSS declarations of the style (example: `"color: #6e7781; font-style: italic"`).
+    pub fn to_css(&self) -> String {
+        let mut declarations = vec![format!("color: {}", self.color)];
+        if self.italic {
+            declarations.push("font-style: italic".into());
+        }
+        if self.bold {
+            declarations.push("font-weight: bold".into());
+        }
+        declarations.join("; ")
+    }
+}
+
+/// Colours of highlighted code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase", default))]
+pub struct Theme {
+    /// Background of code blocks.
+    pub background: String,
+    /// Colour of text that is not a token.
+    pub foreground: String,
+    /// Background of lines highlighted in the meta of a code fence.
+    pub highlighted_line: String,
+    /// Background of lines added in diffs.
+    pub added_line: String,
+    /// Background of lines removed in diffs.
+    pub removed_line: String,
+    /// Colour of line numbers.
+    pub line_number: String,
+    /// Styles of each kind of token, kinds without a style use `foreground`.
+    pub tokens: Vec<(TokenKind, TokenStyle)>,
+}
+
+impl Theme {
+    /// Light theme.
+    pub fn light() -> Self {
+        Self {
+            background: "#ffffff".into(),
+            foreground: "#24292f".into(),
+            highlighted_line: "#fff8c5".into(),
+            added_line: "#e6ffec".into(),
+            removed_line: "#ffebe9".into(),
+            line_number: "#8c959f".into(),
+            tokens: vec![
+                (TokenKind::Constant, TokenStyle::new("#0550ae")),
+                (TokenKind::String, TokenStyle::new("#0a3069")),
+                (TokenKind::Keyword, TokenStyle::new("#cf222e")),
+                (TokenKind::Comment, TokenStyle::italic("#6e7781")),
+                (TokenKind::Entity, TokenStyle::new("#8250df")),
+                (TokenKind::EntityTag, TokenStyle::new("#116329")),
+                (TokenKind::Var, TokenStyle::new("#953800")),
+                (TokenKind::Type, TokenStyle::new("#953800")),
+                (TokenKind::Meta, TokenStyle::new("#8250df")),
+                (TokenKind::Attribute, TokenStyle::new("#0550ae")),
+                (TokenKind::Punctuation, TokenStyle::new("#57606a")),
+                (TokenKind::Error, TokenStyle { bold: true, ..TokenStyle::new("#82071e") }),
+                (TokenKind::Prompt, TokenStyle { bold: true, ..TokenStyle::new("#6e7781") }),
+                (TokenKind::Output, TokenStyle::new("#57606a")),
+                (TokenKind::Inserted, TokenStyle::new("#116329")),
+                (TokenKind::Deleted, TokenStyle::new("#82071e")),
+                (TokenKind::Heading, TokenStyle { bold: true, ..TokenStyle::new("#0550ae") }),
+                (TokenKind::Emphasis, TokenStyle::italic("#24292f")),
+                (TokenKind::Strong, TokenStyle { bold: true, ..TokenStyle::new("#24292f") }),
+            ],
+        }
+    }
+
+    /// Dark theme.
+    pub fn dark() -> Self {
+        Self {
+            background: "#0d1117".into(),
+            foreground: "#c9d1d9".into(),
+            highlighted_line: "#2f2a1e".into(),
+            added_line: "#12261e".into(),
+            removed_line: "#25171c".into(),
+            line_number: "#6e7681".into(),
+            tokens: vec![
+                (TokenKind::Constant, TokenStyle::new("#79c0ff")),
+                (TokenKind::String, TokenStyle::new("#a5d6ff")),
+                (TokenKind::Keyword, TokenStyle::new("#ff7b72")),
+                (TokenKind::Comment, TokenStyle::italic("#8b949e")),
+                (TokenKind::Entity, TokenStyle::new("#d2a8ff")),
+                (TokenKind::EntityTag, TokenStyle::new("#7ee787")),
+                (TokenKind::Var, TokenStyle::new("#ffa657")),
+                (TokenKind::Type, TokenStyle::new("#ffa657")),
+                (TokenKind::Meta, TokenStyle::new("#d2a8ff")),
+                (TokenKind::Attribute, TokenStyle::new("#79c0ff")),
+                (TokenKind::Punctuation, TokenStyle::new("#8b949e")),
+                (TokenKind::Error, TokenStyle { bold: true, ..TokenStyle::new("#ffa198") }),
+                (TokenKind::Prompt, TokenStyle { bold: true, ..TokenStyle::new("#8b949e") }),
+                (TokenKind::Output, TokenStyle::new("#8b949e")),
+                (TokenKind::Inserted, TokenStyle::new("#7ee787")),
+                (TokenKind::Deleted, TokenStyle::new("#ffa198")),
+                (TokenKind::Heading, TokenStyle { bold: true, ..TokenStyle::new("#1f6feb") }),
+                (TokenKind::Emphasis, TokenStyle::italic("#c9d1d9")),
+                (TokenKind::Strong, TokenStyle { bold: true, ..TokenStyle::new("#c9d1d9") }),
+            ],
+        }
+    }
+
+    /// Dark theme with high contrast.
+    pub fn high_contrast() -> Self {
+        Self {
+            background: "#000000".into(),
+            foreground: "#ffffff".into(),
+            highlighted_line: "#3d3000".into(),
+            added_line: "#09311b".into(),
+            removed_line: "#3d0f14".into(),
+            line_number: "#bdc4cc".into(),
+            tokens: vec![
+                (TokenKind::Constant, TokenStyle::new("#91cbff")),
+                (TokenKind::String, TokenStyle::new("#addcff")),
+                (TokenKind::Keyword, TokenStyle { bold: true, ..TokenStyle::new("#ff9492") }),
+                (TokenKind::Comment, TokenStyle::italic("#d9dee3")),
+                (TokenKind::Entity, TokenStyle::new("#dbb7ff")),
+                (TokenKind::EntityTag, TokenStyle::new("#72f088")),
+                (TokenKind::Var, TokenStyle::new("#ffb757")),
+                (TokenKind::Type, TokenStyle::new("#ffb757")),
+                (TokenKind::Meta, TokenStyle::new("#dbb7ff")),
+                (TokenKind::Attribute, TokenStyle::new("#91cbff")),
+                (TokenKind::Punctuation, TokenStyle::new("#d9dee3")),
+                (TokenKind::Error, TokenStyle { bold: true, ..TokenStyle::new("#ffb8b0") }),
+                (TokenKind::Prompt, TokenStyle { bold: true, ..TokenStyle::new("#bdc4cc") }),
+                (TokenKind::Output, TokenStyle::new("#d9dee3")),
+                (TokenKind::Inserted, TokenStyle::new("#72f088")),
+                (TokenKind::Deleted, TokenStyle::new("#ffb8b0")),
+                (TokenKind::Heading, TokenStyle { bold: true, ..TokenStyle::new("#409eff") }),
+                (TokenKind::Emphasis, TokenStyle::italic("#ffffff")),
+                (TokenKind::Strong, TokenStyle { bold: true, ..TokenStyle::new("#ffffff") }),
+            ],
+        }
+    }
+
+    /// Style of `kind`.
+    pub fn token_style(&self, kind: TokenKind) -> Option<&TokenStyle> {
+        self.tokens
+            .iter()
+            .find(|(token_kind, _)| *token_kind == kind)
+            .map(|(_, style)| style)
+    }
+
+    /// Background of lines of `kind`.
+    pub fn line_background(&self, kind: LineKind) -> &str {
+        match kind {
+            LineKind::Added => &self.added_line,
+            LineKind::Removed => &self.removed_line,
+        }
+    }
+
+    /// CSS declarations of a code block.
+    pub fn to_css(&self) -> String {
+        format!("background-color: {}; color: {}", self.background, self.foreground)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn themes() {
+        for theme in [Theme::light(), Theme::dark(), Theme::high_contrast()] {
+            for kind in TokenKind::ALL {
+                assert!(
+                    theme.token_style(kind).is_some(),
+                    "should style every kind of token in bundled themes ({:?})",
+                    kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn to_css() {
+        assert_eq!(
+            Theme::light().token_style(TokenKind::Comment).unwrap().to_css(),
+            "color: #6e7781; font-style: italic",
+            "should serialize token styles"
+        );
+        assert_eq!(
+            Theme::dark().to_css(),
+            "background-color: #0d1117; color: #c9d1d9",
+            "should serialize code block styles"
+        );
+    }
+}
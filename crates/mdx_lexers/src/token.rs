@@ -1,3 +1,5 @@
+use crate::token_kind::TokenKind;
+
 #[derive(PartialEq, Debug)]
 pub enum Token {
     ILLEGAL,
@@ -14,4 +16,44 @@ pub enum Token {
     STRING(Vec<char>),
     KEYWORD(Vec<char>),
     VAR(Vec<char>),
+    TYPE(Vec<char>),
+    META(Vec<char>),
+    ATTRIBUTE(Vec<char>),
+    PUNCTUATION(Vec<char>),
+    ERROR(Vec<char>),
+    PROMPT(Vec<char>),
+    OUTPUT(Vec<char>),
+    INSERTED(Vec<char>),
+    DELETED(Vec<char>),
+    HEADING(Vec<char>),
+    EMPHASIS(Vec<char>),
+    STRONG(Vec<char>),
+}
+
+impl Token {
+    /// Kind of highlighted token, `None` for plain text.
+    pub fn kind(&self) -> Option<TokenKind> {
+        match self {
+            Token::CONSTANT(_) | Token::INT(_) => Some(TokenKind::Constant),
+            Token::STRING(_) => Some(TokenKind::String),
+            Token::KEYWORD(_) => Some(TokenKind::Keyword),
+            Token::COMMENT(_) => Some(TokenKind::Comment),
+            Token::ENTITY(_) => Some(TokenKind::Entity),
+            Token::ENTITYTAG(_) => Some(TokenKind::EntityTag),
+            Token::VAR(_) => Some(TokenKind::Var),
+            Token::TYPE(_) => Some(TokenKind::Type),
+            Token::META(_) => Some(TokenKind::Meta),
+            Token::ATTRIBUTE(_) => Some(TokenKind::Attribute),
+            Token::PUNCTUATION(_) => Some(TokenKind::Punctuation),
+            Token::ERROR(_) => Some(TokenKind::Error),
+            Token::PROMPT(_) => Some(TokenKind::Prompt),
+            Token::OUTPUT(_) => Some(TokenKind::Output),
+            Token::INSERTED(_) => Some(TokenKind::Inserted),
+            Token::DELETED(_) => Some(TokenKind::Deleted),
+            Token::HEADING(_) => Some(TokenKind::Heading),
+            Token::EMPHASIS(_) => Some(TokenKind::Emphasis),
+            Token::STRONG(_) => Some(TokenKind::Strong),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,96 @@
+/// Kind of a highlighted token.
+///
+/// Lexers tell the tokenizer the kind of each token, the tokenizer turns it
+/// into a class name (see `ClassNames`) or an inline style.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serializable", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializable", serde(rename_all = "camelCase"))]
+pub enum TokenKind {
+    /// Constants and numbers.
+    Constant,
+    String,
+    Keyword,
+    Comment,
+    /// Functions, properties and components.
+    Entity,
+    /// Tags of elements.
+    EntityTag,
+    /// Built-in variables.
+    Var,
+    Type,
+    /// Decorators and attributes.
+    Meta,
+    /// Attributes of elements, property names and keys.
+    Attribute,
+    Punctuation,
+    /// Invalid code, when a lexer checks it.
+    Error,
+    /// Prompts of console sessions (`$ `), which cannot be selected.
+    Prompt,
+    /// Output of console sessions.
+    Output,
+    /// Lines added in diffs.
+    Inserted,
+    /// Lines removed in diffs.
+    Deleted,
+    /// Headings of documents.
+    Heading,
+    Emphasis,
+    Strong,
+}
+
+impl TokenKind {
+    /// Every kind of token.
+    pub const ALL: [TokenKind; 19] = [
+        TokenKind::Constant,
+        TokenKind::String,
+        TokenKind::Keyword,
+        TokenKind::Comment,
+        TokenKind::Entity,
+        TokenKind::EntityTag,
+        TokenKind::Var,
+        TokenKind::Type,
+        TokenKind::Meta,
+        TokenKind::Attribute,
+        TokenKind::Punctuation,
+        TokenKind::Error,
+        TokenKind::Prompt,
+        TokenKind::Output,
+        TokenKind::Inserted,
+        TokenKind::Deleted,
+        TokenKind::Heading,
+        TokenKind::Emphasis,
+        TokenKind::Strong,
+    ];
+
+    /// Class name of the kind, without prefix.
+    pub fn class_suffix(self) -> &'static str {
+        match self {
+            TokenKind::Constant => "c",
+            TokenKind::String => "s",
+            TokenKind::Keyword => "k",
+            TokenKind::Comment => "cmt",
+            TokenKind::Entity => "en",
+            TokenKind::EntityTag => "ent",
+            TokenKind::Var => "v",
+            TokenKind::Type => "t",
+            TokenKind::Meta => "m",
+            TokenKind::Attribute => "a",
+            TokenKind::Punctuation => "p",
+            TokenKind::Error => "err",
+            TokenKind::Prompt => "pr",
+            TokenKind::Output => "o",
+            TokenKind::Inserted => "ins",
+            TokenKind::Deleted => "del",
+            TokenKind::Heading => "h",
+            TokenKind::Emphasis => "em",
+            TokenKind::Strong => "st",
+        }
+    }
+
+    /// Whether tokens of the kind can be selected, so that copying code
+    /// leaves out prompts.
+    pub fn is_selectable(self) -> bool {
+        self != TokenKind::Prompt
+    }
+}
@@ -29,15 +29,16 @@
 use crate::hast;
 use crate::swc::{parse_esm_to_tree, parse_expression_to_tree};
 use crate::swc_utils::{
-    create_jsx_attr_name_from_str, create_jsx_name_from_str, inter_element_whitespace,
-    position_to_span,
+    create_jsx_attr_name_from_str, create_jsx_name_from_str, create_object_expression,
+    create_prop_name, create_str_expression, inter_element_whitespace, position_to_span,
 };
 use core::str;
 use markdown::{Location, MdxExpressionKind};
 use swc_core::ecma::ast::{
     Expr, ExprStmt, JSXAttr, JSXAttrOrSpread, JSXAttrValue, JSXClosingElement, JSXClosingFragment,
     JSXElement, JSXElementChild, JSXEmptyExpr, JSXExpr, JSXExprContainer, JSXFragment,
-    JSXOpeningElement, JSXOpeningFragment, Lit, Module, ModuleItem, SpreadElement, Stmt, Str,
+    JSXOpeningElement, JSXOpeningFragment, KeyValueProp, Lit, Module, ModuleItem, Prop,
+    PropOrSpread, SpreadElement, Stmt, Str,
 };
 
 pub const MAGIC_EXPLICIT_MARKER: u32 = 1337;
@@ -199,8 +200,13 @@ fn transform_element(
     while index < element.properties.len() {
         let prop = &element.properties[index];
 
-        // To do: turn style props into objects.
         let value = match &prop.1 {
+            hast::PropertyValue::String(x) if prop.0 == "style" => {
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(Box::new(style_to_object(x))),
+                    span: swc_core::common::DUMMY_SP,
+                }))
+            }
             hast::PropertyValue::Boolean(x) => {
                 // No value is same as `{true}` / Ignore `false`.
                 if *x {
@@ -210,21 +216,21 @@ fn transform_element(
                     continue;
                 }
             }
-            hast::PropertyValue::String(x) => Some(Lit::Str(Str {
+            hast::PropertyValue::String(x) => Some(JSXAttrValue::Lit(Lit::Str(Str {
                 value: x.clone().into(),
                 span: swc_core::common::DUMMY_SP,
                 raw: None,
-            })),
-            hast::PropertyValue::CommaSeparated(x) => Some(Lit::Str(Str {
+            }))),
+            hast::PropertyValue::CommaSeparated(x) => Some(JSXAttrValue::Lit(Lit::Str(Str {
                 value: x.join(", ").into(),
                 span: swc_core::common::DUMMY_SP,
                 raw: None,
-            })),
-            hast::PropertyValue::SpaceSeparated(x) => Some(Lit::Str(Str {
+            }))),
+            hast::PropertyValue::SpaceSeparated(x) => Some(JSXAttrValue::Lit(Lit::Str(Str {
                 value: x.join(" ").into(),
                 span: swc_core::common::DUMMY_SP,
                 raw: None,
-            })),
+            }))),
         };
 
         // Turn property case into either React-specific case, or HTML
@@ -234,7 +240,7 @@ fn transform_element(
 
         attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
             name: create_jsx_attr_name_from_str(&attr_name),
-            value: value.map(JSXAttrValue::Lit),
+            value,
             span: swc_core::common::DUMMY_SP,
         }));
 
@@ -250,6 +256,49 @@ fn transform_element(
     ))))
 }
 
+/// Turn a CSS declaration list (`"color: red; font-style: italic"`) into an
+/// object (`{color: "red", fontStyle: "italic"}`), as React wants it.
+fn style_to_object(value: &str) -> Expr {
+    let mut props = vec![];
+
+    for declaration in value.split(';') {
+        if let Some((name, value)) = declaration.split_once(':') {
+            let name = name.trim();
+            let value = value.trim();
+
+            if name.is_empty() {
+                continue;
+            }
+
+            // Custom properties are kept as-is, others are camel cased.
+            let key = if name.starts_with("--") {
+                name.to_string()
+            } else {
+                let mut key = String::with_capacity(name.len());
+                let mut upper = false;
+                for char in name.chars() {
+                    if char == '-' {
+                        upper = true;
+                    } else if upper {
+                        key.push(char.to_ascii_uppercase());
+                        upper = false;
+                    } else {
+                        key.push(char);
+                    }
+                }
+                key
+            };
+
+            props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: create_prop_name(&key),
+                value: Box::new(create_str_expression(value)),
+            }))));
+        }
+    }
+
+    create_object_expression(props)
+}
+
 /// [`MdxJsxElement`][hast::MdxJsxElement].
 fn transform_mdx_jsx_element(
     context: &mut Context,
@@ -971,6 +1020,30 @@ mod tests {
             "should support an `Element` w/ aria attributes",
         );
 
+        assert_eq!(
+            serialize(
+                &mut hast_util_to_swc(
+                    &hast::Node::Element(hast::Element {
+                        tag_name: "a".into(),
+                        properties: vec![(
+                            "style".into(),
+                            hast::PropertyValue::String(
+                                "color: red; background-color: blue; --x: 1;".into()
+                            ),
+                        )],
+                        children: vec![],
+                        position: None,
+                    }),
+                    None,
+                    None
+                )?
+                .module,
+                None
+            ),
+            "<a style={{\n    color: \"red\",\n    backgroundColor: \"blue\",\n    \"--x\": \"1\"\n}}/>;\n",
+            "should support an `Element` w/ a style attribute",
+        );
+
         Ok(())
     }
 
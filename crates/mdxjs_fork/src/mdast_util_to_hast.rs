@@ -402,6 +402,10 @@ fn transform_code(_state: &mut State, _node: &mdast::Node, code: &mdast::Code) -
         ));
     }
 
+    if let Some(meta) = code.meta.as_ref() {
+        properties.push(("dataMeta".into(), hast::PropertyValue::String(meta.clone())));
+    }
+
     Result::Node(hast::Node::Element(hast::Element {
         tag_name: "pre".into(),
         properties: vec![],
@@ -1370,6 +1374,39 @@ mod tests {
             }),
             "should support a `Code`",
         );
+
+        assert_eq!(
+            mdast_util_to_hast(&mdast::Node::Code(mdast::Code {
+                lang: Some("b".into()),
+                meta: Some("c {1}".into()),
+                value: "a".into(),
+                position: None,
+            })),
+            hast::Node::Element(hast::Element {
+                tag_name: "pre".into(),
+                properties: vec![],
+                children: vec![hast::Node::Element(hast::Element {
+                    tag_name: "code".into(),
+                    properties: vec![
+                        (
+                            "className".into(),
+                            hast::PropertyValue::SpaceSeparated(vec!["language-b".into()]),
+                        ),
+                        (
+                            "dataMeta".into(),
+                            hast::PropertyValue::String("c {1}".into())
+                        ),
+                    ],
+                    children: vec![hast::Node::Text(hast::Text {
+                        value: "a\n".into(),
+                        position: None
+                    })],
+                    position: None
+                })],
+                position: None
+            }),
+            "should support a `Code` (w/ meta)",
+        );
     }
 
     #[test]